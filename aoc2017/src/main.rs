@@ -0,0 +1,484 @@
+//
+// Advent of Code 2017 runner
+//
+// Usage:
+//     aoc2017 run --day 13 [--part 2] [--input puzzle-input.txt]
+//     aoc2017 debug --day 23 [--part 2] --input puzzle-input.txt
+//     aoc2017 profile --day 23 [--part 2] [--format csv] [--input puzzle-input.txt]
+//     aoc2017 disassemble --day 18 [--input puzzle-input.txt]
+//     aoc2017 network --day 18 --topology ring:5 [--input puzzle-input.txt]
+//     aoc2017 export --day 7 [--format json] [--input puzzle-input.txt]
+//
+// Without --part, both parts are reported. Without --input, the puzzle
+// input is read from stdin.
+//
+// The debug command steps through the assembly programs of days 18
+// and 23, reading debugger commands from stdin. The profile command
+// counts the instructions executed by the day 23 coprocessor, as a
+// table or as CSV. The disassemble command lists the program of day 18
+// or 23 with its jump targets labelled and its basic blocks marked.
+// The network command runs copies of the day 18 program, passing
+// messages as in part 2, along the edges of a topology: ring:N, full:N
+// or star:N for N programs, or edges:PATH for a file of "0 -> 1" lines.
+// The export command writes the day 7 tower as a Graphviz digraph or as
+// JSON.
+//
+extern crate day_01;
+extern crate day_02;
+extern crate day_03;
+extern crate day_04;
+extern crate day_05;
+extern crate day_06;
+extern crate day_07;
+extern crate day_08;
+extern crate day_09;
+extern crate day_10;
+extern crate day_11;
+extern crate day_12;
+extern crate day_13;
+extern crate day_14;
+extern crate day_15;
+extern crate day_16;
+extern crate day_17;
+extern crate day_18;
+extern crate day_19;
+extern crate day_20;
+extern crate day_21;
+extern crate day_22;
+extern crate day_23;
+extern crate day_24;
+extern crate day_25;
+extern crate solver;
+
+use std::env;
+use std::fs::File;
+use std::io::{stdin, stdout, Read};
+use std::process;
+
+use solver::{Answer, ParseError, Part, Solver};
+
+const USAGE: &str = "usage: aoc2017 run --day <1-25> [--part <1|2>] [--input <path>]
+       aoc2017 debug --day <18|23> [--part <1|2>] --input <path>
+       aoc2017 profile --day 23 [--part <1|2>] [--format <table|csv>] [--input <path>]
+       aoc2017 disassemble --day <18|23> [--input <path>]
+       aoc2017 network --day 18 --topology <ring:N|full:N|star:N|edges:path> [--input <path>]
+       aoc2017 export --day 7 [--format <dot|json>] [--input <path>]";
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run,
+    Debug,
+    Profile,
+    Disassemble,
+    Network,
+    Export,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Format {
+    Table,
+    Csv,
+    Dot,
+    Json,
+}
+
+#[derive(Debug)]
+struct Options {
+    command: Command,
+    day: Option<u32>,
+    part: Option<Part>,
+    input: Option<String>,
+    format: Option<Format>,
+    topology: Option<String>,
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let command = match args.first().map(|command| command.as_str()) {
+        Some("run") => Command::Run,
+        Some("debug") => Command::Debug,
+        Some("profile") => Command::Profile,
+        Some("disassemble") => Command::Disassemble,
+        Some("network") => Command::Network,
+        Some("export") => Command::Export,
+        Some(command) => return Err(format!("unknown command: {}", command)),
+        None => return Err("missing command".to_string()),
+    };
+
+    let mut options = Options {
+        command,
+        day: None,
+        part: None,
+        input: None,
+        format: None,
+        topology: None,
+    };
+
+    let mut iter = args[1..].iter();
+    while let Some(flag) = iter.next() {
+        let value = match iter.next() {
+            Some(value) => value,
+            None => return Err(format!("missing value for {}", flag)),
+        };
+        match flag.as_str() {
+            "--day" => match value.parse::<u32>() {
+                Ok(day) if (1..=25).contains(&day) => options.day = Some(day),
+                _ => return Err(format!("invalid day: {}", value)),
+            },
+            "--part" => match Part::from_number(value) {
+                Some(part) => options.part = Some(part),
+                None => return Err(format!("invalid part: {}", value)),
+            },
+            "--input" => options.input = Some(value.clone()),
+            "--format" => match value.as_str() {
+                "table" => options.format = Some(Format::Table),
+                "csv" => options.format = Some(Format::Csv),
+                "dot" => options.format = Some(Format::Dot),
+                "json" => options.format = Some(Format::Json),
+                _ => return Err(format!("invalid format: {}", value)),
+            },
+            "--topology" => options.topology = Some(value.clone()),
+            _ => return Err(format!("unknown option: {}", flag)),
+        }
+    }
+
+    if options.day.is_none() {
+        return Err("missing --day".to_string());
+    }
+
+    let assembly = options.day == Some(18) || options.day == Some(23);
+    if options.command == Command::Disassemble && !assembly {
+        return Err("only days 18 and 23 can be disassembled".to_string());
+    }
+
+    if options.command == Command::Debug {
+        if !assembly {
+            return Err("only days 18 and 23 can be debugged".to_string());
+        }
+        // stdin is needed for the debugger commands
+        if options.input.is_none() {
+            return Err("missing --input".to_string());
+        }
+    }
+
+    if options.command == Command::Profile {
+        if options.day != Some(23) {
+            return Err("only day 23 can be profiled".to_string());
+        }
+        match options.format {
+            None | Some(Format::Table) | Some(Format::Csv) => {}
+            Some(_) => return Err("a profile is written as a table or as CSV".to_string()),
+        }
+    }
+
+    if options.command == Command::Export {
+        if options.day != Some(7) {
+            return Err("only day 7 can be exported".to_string());
+        }
+        match options.format {
+            None | Some(Format::Dot) | Some(Format::Json) => {}
+            Some(_) => return Err("an export is written as DOT or as JSON".to_string()),
+        }
+    }
+
+    if options.command == Command::Network {
+        if options.day != Some(18) {
+            return Err("only day 18 can be run as a network".to_string());
+        }
+        match options.topology {
+            Some(ref topology) => {
+                topology_kind(topology)?;
+            }
+            None => return Err("missing --topology".to_string()),
+        }
+    }
+
+    Ok(options)
+}
+
+// Split a topology such as ring:5 into its kind and argument
+fn topology_kind(topology: &str) -> Result<(&str, &str), String> {
+    let invalid = || format!("invalid topology: {}", topology);
+    let colon = topology.find(':').ok_or_else(invalid)?;
+    let (kind, argument) = (&topology[..colon], &topology[colon + 1..]);
+    match kind {
+        "ring" | "full" | "star" => match argument.parse::<usize>() {
+            Ok(programs) if programs > 0 && programs <= day_18::MAX_PROGRAMS => {
+                Ok((kind, argument))
+            }
+            _ => Err(invalid()),
+        },
+        "edges" => Ok((kind, argument)),
+        _ => Err(invalid()),
+    }
+}
+
+fn read_topology(topology: &str) -> Result<day_18::Topology, String> {
+    let (kind, argument) = topology_kind(topology)?;
+    let programs = || argument.parse::<usize>().unwrap();
+    match kind {
+        "ring" => Ok(day_18::Topology::ring(programs())),
+        "full" => Ok(day_18::Topology::fully_connected(programs())),
+        "star" => Ok(day_18::Topology::star(programs())),
+        _ => {
+            let edges = read_input(&Some(argument.to_string()))?;
+            day_18::Topology::parse_edges(&edges).map_err(|e| format!("{}: {}", argument, e))
+        }
+    }
+}
+
+fn read_input(path: &Option<String>) -> Result<String, String> {
+    let mut input = String::new();
+
+    match *path {
+        Some(ref path) => {
+            let mut file = File::open(path).map_err(|e| format!("{}: {}", path, e))?;
+            file.read_to_string(&mut input)
+                .map_err(|e| format!("{}: {}", path, e))?;
+        }
+        None => {
+            stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("stdin: {}", e))?;
+        }
+    }
+
+    Ok(input)
+}
+
+fn solve(day: u32, input: &str, parts: &[Part]) -> Result<Vec<(Part, Answer)>, ParseError> {
+    match day {
+        1 => solver::run::<day_01::Puzzle>(input, parts),
+        2 => solver::run::<day_02::Puzzle>(input, parts),
+        3 => solver::run::<day_03::Puzzle>(input, parts),
+        4 => solver::run::<day_04::Puzzle>(input, parts),
+        5 => solver::run::<day_05::Puzzle>(input, parts),
+        6 => solver::run::<day_06::Puzzle>(input, parts),
+        7 => solver::run::<day_07::Puzzle>(input, parts),
+        8 => solver::run::<day_08::Puzzle>(input, parts),
+        9 => solver::run::<day_09::Puzzle>(input, parts),
+        10 => solver::run::<day_10::Puzzle>(input, parts),
+        11 => solver::run::<day_11::Puzzle>(input, parts),
+        12 => solver::run::<day_12::Puzzle>(input, parts),
+        13 => solver::run::<day_13::Puzzle>(input, parts),
+        14 => solver::run::<day_14::Puzzle>(input, parts),
+        15 => solver::run::<day_15::Puzzle>(input, parts),
+        16 => solver::run::<day_16::Puzzle>(input, parts),
+        17 => solver::run::<day_17::Puzzle>(input, parts),
+        18 => solver::run::<day_18::Puzzle>(input, parts),
+        19 => solver::run::<day_19::Puzzle>(input, parts),
+        20 => solver::run::<day_20::Puzzle>(input, parts),
+        21 => solver::run::<day_21::Puzzle>(input, parts),
+        22 => solver::run::<day_22::Puzzle>(input, parts),
+        23 => solver::run::<day_23::Puzzle>(input, parts),
+        24 => solver::run::<day_24::Puzzle>(input, parts),
+        25 => solver::run::<day_25::Puzzle>(input, parts),
+        _ => unreachable!("day {} out of range", day),
+    }
+}
+
+fn debug(day: u32, input: &str, part: Part) -> Result<(), ParseError> {
+    let stdin = stdin();
+    let stdout = stdout();
+    let result = match day {
+        18 => day_18::Puzzle::parse(input)?.debug(part, stdin.lock(), stdout.lock()),
+        23 => day_23::Puzzle::parse(input)?.debug(part, stdin.lock(), stdout.lock()),
+        _ => unreachable!("day {} cannot be debugged", day),
+    };
+    if let Err(e) = result {
+        eprintln!("aoc2017: {}", e);
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn profile(input: &str, part: Part, format: Option<Format>) -> Result<(), ParseError> {
+    let profile = match day_23::Puzzle::parse(input)?.profile(part) {
+        Ok(profile) => profile,
+        Err(e) => {
+            eprintln!("aoc2017: {}", e);
+            process::exit(1);
+        }
+    };
+    if format == Some(Format::Csv) {
+        let stdout = stdout();
+        if let Err(e) = profile.write_csv(stdout.lock()) {
+            eprintln!("aoc2017: {}", e);
+            process::exit(1);
+        }
+    } else {
+        println!("{}", profile);
+    }
+    Ok(())
+}
+
+fn disassemble(day: u32, input: &str) -> Result<(), ParseError> {
+    let listing = match day {
+        18 => day_18::Puzzle::parse(input)?.disassemble(),
+        23 => day_23::Puzzle::parse(input)?.disassemble(),
+        _ => unreachable!("day {} cannot be disassembled", day),
+    };
+    print!("{}", listing);
+    Ok(())
+}
+
+fn network(input: &str, topology: &day_18::Topology) -> Result<(), ParseError> {
+    let reports = match day_18::Puzzle::parse(input)?.network(topology) {
+        Ok(reports) => reports,
+        Err(e) => {
+            eprintln!("aoc2017: {}", e);
+            process::exit(1);
+        }
+    };
+    println!("{:>7} {:>10} {:>10}  exit", "program", "sent", "received");
+    for report in reports {
+        println!("{}", report);
+    }
+    Ok(())
+}
+
+fn export(input: &str, format: Option<Format>) -> Result<(), ParseError> {
+    let puzzle = day_07::Puzzle::parse(input)?;
+    let stdout = stdout();
+    let result = match format {
+        Some(Format::Json) => puzzle.write_json(stdout.lock()),
+        _ => puzzle.write_dot(stdout.lock()),
+    };
+    if let Err(e) = result {
+        eprintln!("aoc2017: {}", e);
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let options = parse_options(&args).unwrap_or_else(|e| {
+        eprintln!("aoc2017: {}", e);
+        eprintln!("{}", USAGE);
+        process::exit(2);
+    });
+
+    let topology = options.topology.as_ref().map(|topology| {
+        read_topology(topology).unwrap_or_else(|e| {
+            eprintln!("aoc2017: {}", e);
+            process::exit(1);
+        })
+    });
+
+    let input = read_input(&options.input).unwrap_or_else(|e| {
+        eprintln!("aoc2017: {}", e);
+        process::exit(1);
+    });
+
+    let parts = match options.part {
+        Some(part) => vec![part],
+        None => Part::all(),
+    };
+
+    let result = match options.command {
+        Command::Run => solve(options.day.unwrap(), &input, &parts).map(|results| {
+            if let Err((part, e)) = solver::report(&results) {
+                eprintln!("aoc2017: day {} {} failed: {}", options.day.unwrap(), part, e);
+                process::exit(1);
+            }
+        }),
+        Command::Debug => debug(options.day.unwrap(), &input, parts[0]),
+        Command::Profile => profile(&input, parts[0], options.format),
+        Command::Disassemble => disassemble(options.day.unwrap(), &input),
+        Command::Network => network(&input, &topology.unwrap()),
+        Command::Export => export(&input, options.format),
+    };
+
+    match result {
+        Ok(()) => {}
+        Err(e) => {
+            let source = options.input.unwrap_or_else(|| "stdin".to_string());
+            eprintln!("aoc2017: invalid puzzle input for day {}", options.day.unwrap());
+            eprintln!("{}: {}", source, e);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_options_full() {
+        let options = parse_options(&args("run --day 13 --part 2 --input day-13.txt")).unwrap();
+        assert_eq!(Some(13), options.day);
+        assert_eq!(Some(Part::Two), options.part);
+        assert_eq!(Some("day-13.txt".to_string()), options.input);
+    }
+
+    #[test]
+    fn parse_options_rejects_bad_input() {
+        assert!(parse_options(&args("")).is_err());
+        assert!(parse_options(&args("run")).is_err());
+        assert!(parse_options(&args("run --day 26")).is_err());
+        assert!(parse_options(&args("run --day 1 --part 3")).is_err());
+        assert!(parse_options(&args("run --day 1 --part")).is_err());
+        assert!(parse_options(&args("walk --day 1")).is_err());
+    }
+
+    #[test]
+    fn parse_options_debug() {
+        let options = parse_options(&args("debug --day 18 --input day-18.txt")).unwrap();
+        assert_eq!(Command::Debug, options.command);
+        assert!(parse_options(&args("debug --day 13 --input day-13.txt")).is_err());
+        assert!(parse_options(&args("debug --day 23")).is_err());
+    }
+
+    #[test]
+    fn parse_options_profile() {
+        let options = parse_options(&args("profile --day 23 --format csv")).unwrap();
+        assert_eq!(Command::Profile, options.command);
+        assert_eq!(Some(Format::Csv), options.format);
+        assert!(parse_options(&args("profile --day 18")).is_err());
+        assert!(parse_options(&args("profile --day 23 --format xml")).is_err());
+        assert!(parse_options(&args("profile --day 23 --format json")).is_err());
+        assert!(parse_options(&args("disassemble --day 18")).is_ok());
+        assert!(parse_options(&args("disassemble --day 19")).is_err());
+    }
+
+    #[test]
+    fn parse_options_network() {
+        let options = parse_options(&args("network --day 18 --topology star:4")).unwrap();
+        assert_eq!(Some("star:4".to_string()), options.topology);
+        assert!(parse_options(&args("network --day 18")).is_err());
+        assert!(parse_options(&args("network --day 18 --topology ring:0")).is_err());
+        assert!(parse_options(&args("network --day 18 --topology ring:1001")).is_err());
+        assert!(parse_options(&args("network --day 18 --topology mesh:3")).is_err());
+        assert!(parse_options(&args("network --day 18 --topology edges:net.txt")).is_ok());
+    }
+
+    #[test]
+    fn parse_options_export() {
+        let options = parse_options(&args("export --day 7 --format json")).unwrap();
+        assert_eq!(Command::Export, options.command);
+        assert_eq!(Some(Format::Json), options.format);
+        assert!(parse_options(&args("export --day 7")).is_ok());
+        assert!(parse_options(&args("export --day 8")).is_err());
+        assert!(parse_options(&args("export --day 7 --format csv")).is_err());
+    }
+
+    #[test]
+    fn solve_day_01() {
+        let results = solve(1, "1122\n", &Part::all()).unwrap();
+        assert_eq!(Ok(Some("3".to_string())), results[0].1);
+        assert_eq!(Ok(Some("0".to_string())), results[1].1);
+    }
+
+    #[test]
+    fn solve_reports_parse_error() {
+        let e = solve(13, "0: 3\n1: 2 \n", &Part::all()).unwrap_err();
+        assert_eq!((2, 4), (e.line, e.column));
+        assert_eq!("1: 2 ", e.text);
+    }
+}
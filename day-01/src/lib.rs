@@ -0,0 +1,121 @@
+extern crate solver;
+
+use solver::{Answer, ParseError, Solver};
+
+fn summer1(digits: &[u32]) -> u32 {
+    let mut sum = 0;
+
+    // Proceed forward through the list of digits
+    for j in 0..(digits.len() - 1) {
+        if digits[j] == digits[j + 1] {
+            sum += digits[j];
+        }
+    }
+
+    // Check if last digit matches first
+    if digits[digits.len() - 1] == digits[0] {
+        sum += digits[0];
+    }
+
+    sum
+}
+
+fn summer2(digits: &[u32]) -> u32 {
+    let mut sum = 0;
+
+    let half_len = digits.len() / 2;
+
+    // Proceed forward through the list of digits
+    for j in 0..half_len {
+        if digits[j] == digits[j + half_len] {
+            sum += digits[j];
+        }
+    }
+
+    sum + sum
+}
+
+fn parser(input: &str) -> Result<Vec<u32>, ParseError> {
+    // Convert input into vector of u32s; report the first non-digit
+    let line = input.trim();
+    line.chars()
+        .enumerate()
+        .map(|(j, c)| {
+            c.to_digit(10)
+                .ok_or_else(|| ParseError::new(1, j + 1, line, format!("expected digit: {:?}", c)))
+        })
+        .collect()
+}
+
+pub struct Puzzle {
+    digits: Vec<u32>,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        Ok(Puzzle {
+            digits: parser(input)?,
+        })
+    }
+
+    fn part1(&self) -> Answer {
+        Ok(Some(summer1(&self.digits).to_string()))
+    }
+
+    fn part2(&self) -> Answer {
+        Ok(Some(summer2(&self.digits).to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summer1_provided_tests() {
+        assert_eq!(3, summer1(&[1, 1, 2, 2]));
+        assert_eq!(4, summer1(&[1, 1, 1, 1]));
+        assert_eq!(0, summer1(&[1, 2, 3, 4]));
+        assert_eq!(9, summer1(&[9, 1, 2, 1, 2, 1, 2, 9]));
+    }
+
+    #[test]
+    fn summer1_other_tests() {
+        assert_eq!(23, summer1(&vec![1, 2, 2, 3, 4, 5, 5, 6, 7, 8, 8, 8, 9, 0]));
+    }
+
+    #[test]
+    fn summer2_provided_tests() {
+        assert_eq!(6, summer2(&[1, 2, 1, 2]));
+        assert_eq!(0, summer2(&[1, 2, 2, 1]));
+        assert_eq!(4, summer2(&[1, 2, 3, 4, 2, 5]));
+        assert_eq!(12, summer2(&[1, 2, 3, 1, 2, 3]));
+        assert_eq!(4, summer2(&[1, 2, 1, 3, 1, 4, 1, 5]));
+    }
+
+    #[test]
+    fn parser_provided_tests() {
+        assert_eq!(Ok(vec![1, 1, 2, 2]), parser("1122"));
+        assert_eq!(Ok(vec![1, 1, 1, 1]), parser("1111"));
+        assert_eq!(Ok(vec![1, 2, 3, 4]), parser("1234"));
+        assert_eq!(
+            Ok(vec![9, 1, 2, 1, 2, 1, 2, 9]),
+            parser("91212129")
+        );
+    }
+
+    #[test]
+    fn parser_other_tests() {
+        assert_eq!(
+            Ok(vec![1, 2, 2, 3, 4, 5, 5, 6, 7, 8, 8, 8, 9, 0]),
+            parser("12234556788890")
+        );
+    }
+
+    #[test]
+    fn parser_reports_non_digit() {
+        let e = parser("12a4\n").unwrap_err();
+        assert_eq!((1, 3), (e.line, e.column));
+        assert_eq!("12a4", e.text);
+    }
+}
@@ -0,0 +1,99 @@
+extern crate solver;
+
+use solver::{Answer, ParseError, Solver};
+
+fn process_row_part_1(row: &[u32]) -> u32 {
+    let mut min: u32 = row[0];
+    let mut max: u32 = row[0];
+
+    for &x in &row[1..] {
+        if x < min {
+            min = x;
+        }
+        if x > max {
+            max = x;
+        }
+    }
+
+    max - min
+}
+
+fn process_row_part_2(row: &[u32]) -> u32 {
+    let mut result = 0;
+
+    for x in row {
+        let row_clone = row.to_owned();
+        for y in row_clone {
+            if *x == y {
+                continue;
+            }
+            if *x % y == 0 {
+                result = *x / y;
+                break;
+            }
+        }
+    }
+
+    result
+}
+
+fn process_rows<F>(rows: &[Vec<u32>], f: F) -> u32
+where
+    F: Fn(&[u32]) -> u32,
+{
+    let mut sum: u32 = 0;
+
+    for row in rows {
+        sum += f(row);
+    }
+
+    sum
+}
+
+fn parse_line(line: &str) -> Result<Vec<u32>, ParseError> {
+    line.split_whitespace()
+        .map(|s| {
+            s.parse()
+                .map_err(|e| ParseError::token(1, line, s, e))
+        })
+        .collect()
+}
+
+pub struct Puzzle {
+    rows: Vec<Vec<u32>>,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        Ok(Puzzle {
+            rows: solver::parse_lines(input, parse_line)?,
+        })
+    }
+
+    fn part1(&self) -> Answer {
+        Ok(Some(process_rows(&self.rows, process_row_part_1).to_string()))
+    }
+
+    fn part2(&self) -> Answer {
+        Ok(Some(process_rows(&self.rows, process_row_part_2).to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_1_process_row_provided_tests() {
+        assert_eq!(8, process_row_part_1(&[5, 1, 9, 5]));
+        assert_eq!(4, process_row_part_1(&[7, 5, 3]));
+        assert_eq!(6, process_row_part_1(&[2, 4, 6, 8]));
+    }
+
+    #[test]
+    fn part_2_process_row_provided_tests() {
+        assert_eq!(4, process_row_part_2(&[5, 9, 2, 8]));
+        assert_eq!(3, process_row_part_2(&[9, 4, 7, 3]));
+        assert_eq!(2, process_row_part_2(&[3, 8, 6, 5]));
+    }
+}
@@ -0,0 +1,275 @@
+//
+// Advent of Code 2017, Day 3
+//
+// To run both parts:
+//     cat puzzle-input.txt | cargo run
+//
+// Key Observations:
+//
+// - The spiral is mapped on a two dimensional grid. Each square has an x
+//   and a y value, in relation to the origin at square 1.
+//
+// - The spiral builds starting as follows: 1 right, 1 up, 2 left, 2 down,
+//   3 right, 3 up, four left, 4 down... The code in Spiral::create_grid()
+//   marked "Turn the corner" continues this pattern.
+//
+// - The sum for a square can use 2, 3 or 4 previous values, depending on
+//   how soon the spiral will turn. Square::summable_squares() builds a
+//   list of these adjacent values. Spiral::calculate_sum() searches
+//   backward through the grid vector, matching on grid position and adding
+//   up the new sum.
+//
+extern crate solver;
+
+use solver::{Answer, ParseError, Part, Solver};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Direction {
+    Right,
+    Up,
+    Left,
+    Down,
+}
+
+#[derive(Debug)]
+struct Square {
+    x: i32,
+    y: i32,
+    sum: Option<u32>,
+}
+
+impl Square {
+    fn initial() -> Square {
+        Square {
+            x: 0,
+            y: 0,
+            sum: Some(1),
+        }
+    }
+
+    fn create_next(&self, direction: Direction) -> Square {
+        let (x, y): (i32, i32) = match direction {
+            Direction::Right => (self.x + 1, self.y),
+            Direction::Up => (self.x, self.y + 1),
+            Direction::Left => (self.x - 1, self.y),
+            Direction::Down => (self.x, self.y - 1),
+        };
+
+        Square {
+            x,
+            y,
+            sum: None,
+        }
+    }
+
+    fn summable_squares(&self, direction: Direction, position: u32) -> Vec<(i32, i32)> {
+        let mut result: Vec<(i32, i32)> = Vec::new();
+
+        match direction {
+            Direction::Right => {
+                result.push((self.x - 1, self.y));
+                result.push((self.x - 1, self.y + 1));
+            }
+            Direction::Up => {
+                result.push((self.x, self.y - 1));
+                result.push((self.x - 1, self.y - 1));
+            }
+            Direction::Left => {
+                result.push((self.x + 1, self.y));
+                result.push((self.x + 1, self.y - 1));
+            }
+            Direction::Down => {
+                result.push((self.x, self.y + 1));
+                result.push((self.x + 1, self.y + 1));
+            }
+        };
+        if position == 0 {
+            return result;
+        }
+
+        match direction {
+            Direction::Right => {
+                result.push((self.x, self.y + 1));
+            }
+            Direction::Up => {
+                result.push((self.x - 1, self.y));
+            }
+            Direction::Left => {
+                result.push((self.x, self.y - 1));
+            }
+            Direction::Down => {
+                result.push((self.x + 1, self.y));
+            }
+        };
+        if position == 1 {
+            return result;
+        }
+
+        match direction {
+            Direction::Right => {
+                result.push((self.x + 1, self.y + 1));
+            }
+            Direction::Up => {
+                result.push((self.x - 1, self.y + 1));
+            }
+            Direction::Left => {
+                result.push((self.x - 1, self.y - 1));
+            }
+            Direction::Down => {
+                result.push((self.x + 1, self.y - 1));
+            }
+        };
+        result
+    }
+
+    fn calculate_steps(&self) -> u32 {
+        (self.x.abs() + self.y.abs()) as u32
+    }
+}
+
+struct Spiral {
+    limit: usize,
+    grid: Vec<Square>,
+    direction: Direction,
+    distance: u32,
+    step: u32,
+    sum_beyond_limit: Option<u32>,
+}
+
+impl Spiral {
+    fn new(limit: usize) -> Spiral {
+        Spiral {
+            limit,
+            grid: Vec::with_capacity(limit),
+            direction: Direction::Right,
+            distance: 1,
+            step: 0,
+            sum_beyond_limit: None,
+        }
+    }
+
+    fn turn(&mut self) {
+        self.direction = match self.direction {
+            Direction::Right => Direction::Up,
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+        }
+    }
+
+    //
+    // For part 1, build the whole grid up to the limit. For part 2,
+    // also calculate sums, and stop at the first sum beyond the limit.
+    //
+    fn build_grid(&mut self, part: Part) {
+        let mut square = Square::initial();
+        loop {
+            let next_square = square.create_next(self.direction);
+            self.grid.push(square);
+
+            square = next_square;
+            self.step += 1;
+
+            if part == Part::Two {
+                self.calculate_sum(&mut square);
+            }
+
+            // Turn the corner
+            if self.step == self.distance {
+                self.step = 0;
+                if self.direction == Direction::Up || self.direction == Direction::Down {
+                    self.distance += 1;
+                }
+                self.turn();
+            }
+
+            let done = match part {
+                Part::One => self.grid.len() == self.limit,
+                Part::Two => self.sum_beyond_limit.is_some(),
+            };
+            if done {
+                break;
+            }
+        }
+    }
+
+    fn calculate_sum(&mut self, square: &mut Square) {
+        let summable_squares = square.summable_squares(self.direction, self.distance - self.step);
+        let mut sum = 0;
+        for ss in summable_squares {
+            for q in self.grid.iter().rev() {
+                if ss.0 == q.x && ss.1 == q.y {
+                    sum += q.sum.unwrap();
+                    break;
+                }
+            }
+        }
+        square.sum = Some(sum);
+        if sum > (self.limit as u32) {
+            self.sum_beyond_limit = Some(sum);
+        }
+    }
+
+    fn steps(&self) -> u32 {
+        self.grid[self.limit - 1].calculate_steps()
+    }
+}
+
+pub struct Puzzle {
+    limit: usize,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let line = input.trim();
+        let limit = line.parse::<usize>()
+            .map_err(|e| ParseError::token(1, line, line, e))?;
+
+        Ok(Puzzle { limit })
+    }
+
+    fn part1(&self) -> Answer {
+        let mut spiral = Spiral::new(self.limit);
+        spiral.build_grid(Part::One);
+        Ok(Some(spiral.steps().to_string()))
+    }
+
+    fn part2(&self) -> Answer {
+        let mut spiral = Spiral::new(self.limit);
+        spiral.build_grid(Part::Two);
+        Ok(spiral.sum_beyond_limit.map(|sum| sum.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn steps(limit: usize) -> u32 {
+        let mut spiral = Spiral::new(limit);
+        spiral.build_grid(Part::One);
+        spiral.steps()
+    }
+
+    fn sum_beyond_limit(limit: usize) -> Option<u32> {
+        let mut spiral = Spiral::new(limit);
+        spiral.build_grid(Part::Two);
+        spiral.sum_beyond_limit
+    }
+
+    #[test]
+    fn part1_provided_tests() {
+        assert_eq!(0, steps(1));
+        assert_eq!(3, steps(12));
+        assert_eq!(2, steps(23));
+        assert_eq!(31, steps(1024));
+    }
+
+    #[test]
+    fn part2_provided_tests() {
+        assert_eq!(Some(4), sum_beyond_limit(2));
+        assert_eq!(Some(10), sum_beyond_limit(5));
+        assert_eq!(Some(26), sum_beyond_limit(25));
+        assert_eq!(Some(806), sum_beyond_limit(747));
+    }
+}
@@ -0,0 +1,97 @@
+//
+// To run both parts:
+//     cat puzzle-input.txt | cargo run
+//
+
+extern crate solver;
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use solver::{Answer, ParseError, Part, Solver};
+
+fn sort(word: &str, part: Part) -> String {
+    match part {
+        // For part 1, do not sort
+        Part::One => word.to_string(),
+        Part::Two => {
+            let mut chars: Vec<char> = word.chars().collect();
+            chars.sort();
+            chars.into_iter().collect()
+        }
+    }
+}
+
+//
+// Return true if each word in the passphrase is unique;
+// otherwise, return false
+//
+fn check_passphrase(passphrase: &str, part: Part) -> bool {
+    let words: Vec<&str> = passphrase.split_whitespace().collect();
+
+    let mut hm = HashMap::<String, bool>::new();
+
+    for word in words {
+        let sorted_word = sort(word, part);
+        match hm.entry(sorted_word) {
+            Entry::Occupied(_) => return false,
+            Entry::Vacant(v) => v.insert(true),
+        };
+    }
+
+    true
+}
+
+pub struct Puzzle {
+    passphrases: Vec<String>,
+}
+
+impl Puzzle {
+    fn count_valid(&self, part: Part) -> usize {
+        self.passphrases
+            .iter()
+            .filter(|p| check_passphrase(p, part))
+            .count()
+    }
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        Ok(Puzzle {
+            passphrases: input.lines().map(|line| line.to_string()).collect(),
+        })
+    }
+
+    fn part1(&self) -> Answer {
+        Ok(Some(self.count_valid(Part::One).to_string()))
+    }
+
+    fn part2(&self) -> Answer {
+        Ok(Some(self.count_valid(Part::Two).to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_provided_tests() {
+        assert!(check_passphrase("aa bb cc dd ee", Part::One));
+        assert!(!check_passphrase("aa bb cc dd aa", Part::One));
+        assert!(check_passphrase("aa bb cc dd aaa", Part::One));
+    }
+
+    #[test]
+    fn part2_provided_tests() {
+        assert!(check_passphrase("abcde fghij", Part::Two));
+        assert!(!check_passphrase("abcde xyz ecdab", Part::Two));
+        assert!(check_passphrase("a ab abc abd abf abj", Part::Two));
+        assert!(check_passphrase("iiii oiii ooii oooi oooo", Part::Two));
+        assert!(!check_passphrase("oiii ioii iioi iiio", Part::Two));
+    }
+
+    #[test]
+    fn anagrams_are_valid_in_part1() {
+        assert!(check_passphrase("abcde xyz ecdab", Part::One));
+    }
+}
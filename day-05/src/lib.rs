@@ -0,0 +1,83 @@
+//
+// To run both parts:
+//     cat puzzle-input.txt | cargo run
+//
+
+extern crate solver;
+
+use solver::{Answer, ParseError, Part, Solver};
+
+fn modify_instruction(instructions: &mut [i32], index: usize, instruction: i32, part: Part) {
+    if part == Part::Two && instruction >= 3 {
+        instructions[index] -= 1;
+    } else {
+        instructions[index] += 1;
+    }
+}
+
+fn jump_through_maze(instructions: &mut [i32], part: Part) -> u32 {
+    let mut instruction: i32;
+    let mut index: usize = 0;
+    let mut new_index: i32;
+    let mut steps: u32 = 0;
+
+    loop {
+        instruction = instructions[index];
+        steps += 1;
+
+        new_index = (index as i32) + instruction;
+
+        // Determine if we have exited the maze
+        if new_index < 0 || new_index as usize >= instructions.len() {
+            break;
+        }
+
+        modify_instruction(instructions, index, instruction, part);
+
+        index = new_index as usize;
+    }
+
+    steps
+}
+
+pub struct Puzzle {
+    instructions: Vec<i32>,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        Ok(Puzzle {
+            instructions: solver::parse_lines(input, |line| {
+                line.parse::<i32>()
+                    .map_err(|e| ParseError::token(1, line, line, e))
+            })?,
+        })
+    }
+
+    fn part1(&self) -> Answer {
+        let mut instructions = self.instructions.clone();
+        Ok(Some(jump_through_maze(&mut instructions, Part::One).to_string()))
+    }
+
+    fn part2(&self) -> Answer {
+        let mut instructions = self.instructions.clone();
+        Ok(Some(jump_through_maze(&mut instructions, Part::Two).to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_provided_test() {
+        let mut instructions = vec![0, 3, 0, 1, -3];
+        assert_eq!(5, jump_through_maze(&mut instructions, Part::One));
+    }
+
+    #[test]
+    fn part2_provided_test() {
+        let mut instructions = vec![0, 3, 0, 1, -3];
+        assert_eq!(10, jump_through_maze(&mut instructions, Part::Two));
+    }
+}
@@ -0,0 +1,99 @@
+extern crate solver;
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use solver::{Answer, ParseError, Solver};
+
+struct State {
+    hm: HashMap<Vec<u32>, u32>,
+}
+
+impl State {
+    fn new() -> State {
+        State {
+            hm: HashMap::<Vec<u32>, u32>::new(),
+        }
+    }
+
+    fn track(&mut self, banks: &[u32], cycle: u32) -> Option<u32> {
+        match self.hm.entry((*banks).to_vec()) {
+            Entry::Occupied(o) => return Some(*o.get()),
+            Entry::Vacant(v) => v.insert(cycle),
+        };
+        None
+    }
+}
+
+fn find_largest_bank(banks: &[u32]) -> usize {
+    let mut largest_bank: u32 = 0;
+    let mut index_result: usize = 0;
+
+    for (index, &bank) in banks.iter().enumerate() {
+        if bank > largest_bank {
+            largest_bank = bank;
+            index_result = index;
+        }
+    }
+    index_result
+}
+
+fn redistribute(banks: &mut [u32], source: usize) {
+    let mut blocks: u32 = banks[source];
+    banks[source] = 0;
+
+    let mut index: usize = source;
+    loop {
+        index += 1;
+        if index == banks.len() {
+            index = 0;
+        }
+        banks[index] += 1;
+        blocks -= 1;
+        if blocks == 0 {
+            return;
+        }
+    }
+}
+
+//
+// Return the number of cycles before a configuration repeats, and
+// the length of the infinite loop
+//
+fn process(mut banks: Vec<u32>) -> (u32, u32) {
+    let mut state = State::new();
+    let mut cycles: u32 = 0;
+
+    loop {
+        if let Some(loop_start) = state.track(&banks, cycles) {
+            return (cycles, cycles - loop_start);
+        }
+        let index = find_largest_bank(&banks);
+        redistribute(&mut banks, index);
+        cycles += 1;
+    }
+}
+
+pub struct Puzzle {
+    banks: Vec<u32>,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let line = input.trim();
+        let banks = line.split_whitespace()
+            .map(|x| x.parse::<u32>().map_err(|e| ParseError::token(1, line, x, e)))
+            .collect::<Result<Vec<u32>, ParseError>>()?;
+
+        Ok(Puzzle { banks })
+    }
+
+    fn part1(&self) -> Answer {
+        let (cycles, _) = process(self.banks.clone());
+        Ok(Some(cycles.to_string()))
+    }
+
+    fn part2(&self) -> Answer {
+        let (_, loop_length) = process(self.banks.clone());
+        Ok(Some(loop_length.to_string()))
+    }
+}
@@ -0,0 +1,190 @@
+//
+// Changes to a tower whose disc weights have been calculated. Rather
+// than weigh every disc again, each change adjusts the disc weights of
+// the programs below the one changed, down to the bottom, and looks
+// again at whether those discs balance. No other disc is affected.
+//
+use {Program, Tower};
+
+use std::error;
+use std::fmt::{self, Display, Formatter};
+
+// Why a change could not be made to the tower
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditError {
+    Unknown(String),
+    Duplicate(String),
+    InvalidName(String),
+    // The bottom program cannot be moved or removed
+    Bottom(String),
+    // A program cannot be moved onto its own disc or any above it
+    Cycle { program: String, holder: String },
+    // The total weight of the program would not fit in a u32
+    Overflow(String),
+}
+
+impl Display for EditError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            EditError::Unknown(ref program) => write!(f, "no program is named {}", program),
+            EditError::Duplicate(ref program) => write!(f, "{} is already in the tower", program),
+            EditError::InvalidName(ref program) => {
+                write!(f, "{:?} is not a name of lower case letters", program)
+            }
+            EditError::Bottom(ref program) => write!(f, "{} is at the bottom of the tower", program),
+            EditError::Cycle {
+                ref program,
+                ref holder,
+            } => write!(f, "{} cannot hold {}, which is below it", holder, program),
+            EditError::Overflow(ref program) => {
+                write!(f, "the total weight of {} would be too large", program)
+            }
+        }
+    }
+}
+
+impl error::Error for EditError {}
+
+impl Tower {
+    fn known(&self, name: &str) -> Result<(), EditError> {
+        if self.programs_hm.contains_key(name) {
+            Ok(())
+        } else {
+            Err(EditError::Unknown(name.to_string()))
+        }
+    }
+
+    fn above_bottom(&self, name: &str) -> Result<(), EditError> {
+        self.known(name)?;
+        if name == self.bottom {
+            Err(EditError::Bottom(name.to_string()))
+        } else {
+            Ok(())
+        }
+    }
+
+    //
+    // Replace old weight with new in the disc weight of each program.
+    // Nothing is changed if that would take the total weight of any of
+    // them beyond a u32.
+    //
+    fn shift(&mut self, below: &[String], old: u32, new: u32) -> Result<(), EditError> {
+        let mut disc_weights = Vec::new();
+        for name in below {
+            let program = &self.programs_hm[name];
+            let disc_weight = program
+                .disc_weight
+                .unwrap()
+                .checked_sub(old)
+                .and_then(|disc_weight| disc_weight.checked_add(new))
+                .filter(|&disc_weight| program.weight.checked_add(disc_weight).is_some())
+                .ok_or_else(|| EditError::Overflow(name.clone()))?;
+            disc_weights.push(disc_weight);
+        }
+        for (name, disc_weight) in below.iter().zip(disc_weights) {
+            self.programs_hm.get_mut(name).unwrap().disc_weight = Some(disc_weight);
+        }
+        Ok(())
+    }
+
+    fn detach(&mut self, name: &str) -> Result<Vec<String>, EditError> {
+        let below = self.below(name);
+        let total = self.programs_hm[name].total_weight();
+        self.shift(&below, total, 0)?;
+        let holder = self.holders.remove(name).unwrap();
+        self.programs_hm
+            .get_mut(&holder)
+            .unwrap()
+            .disc
+            .retain(|subname| subname != name);
+        Ok(below)
+    }
+
+    // Put name, of the given total weight, onto the disc of holder
+    fn attach(&mut self, name: &str, total: u32, holder: &str) -> Result<Vec<String>, EditError> {
+        let mut below = vec![holder.to_string()];
+        below.extend(self.below(holder));
+        self.shift(&below, 0, total)?;
+        self.holders.insert(name.to_string(), holder.to_string());
+        self.programs_hm
+            .get_mut(holder)
+            .unwrap()
+            .disc
+            .push(name.to_string());
+        Ok(below)
+    }
+
+    pub fn set_weight(&mut self, name: &str, weight: u32) -> Result<(), EditError> {
+        self.known(name)?;
+
+        let below = self.below(name);
+        let program = &self.programs_hm[name];
+        let old = program.weight;
+        if weight.checked_add(program.disc_weight.unwrap()).is_none() {
+            return Err(EditError::Overflow(name.to_string()));
+        }
+        self.shift(&below, old, weight)?;
+        self.programs_hm.get_mut(name).unwrap().weight = weight;
+        self.rebalance(&below);
+        Ok(())
+    }
+
+    // Move name, with all the programs above it, onto the disc of holder
+    pub fn move_program(&mut self, name: &str, holder: &str) -> Result<(), EditError> {
+        self.above_bottom(name)?;
+        self.known(holder)?;
+        if holder == name || self.below(holder).iter().any(|below| below == name) {
+            return Err(EditError::Cycle {
+                program: name.to_string(),
+                holder: holder.to_string(),
+            });
+        }
+
+        // No total weight can grow beyond that of the bottom program,
+        // so moving a program never fails for its weight
+        let total = self.programs_hm[name].total_weight();
+        let old_below = self.detach(name)?;
+        let new_below = self.attach(name, total, holder)?;
+        self.rebalance(&old_below);
+        self.rebalance(&new_below);
+        Ok(())
+    }
+
+    // Add a program with an empty disc onto the disc of holder
+    pub fn add_program(&mut self, name: &str, weight: u32, holder: &str) -> Result<(), EditError> {
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_lowercase()) {
+            return Err(EditError::InvalidName(name.to_string()));
+        }
+        if self.programs_hm.contains_key(name) {
+            return Err(EditError::Duplicate(name.to_string()));
+        }
+        self.known(holder)?;
+
+        let below = self.attach(name, weight, holder)?;
+        self.programs_hm.insert(
+            name.to_string(),
+            Program {
+                name: name.to_string(),
+                weight,
+                disc: Vec::new(),
+                disc_weight: Some(0),
+            },
+        );
+        self.rebalance(&below);
+        Ok(())
+    }
+
+    // Remove name, with all the programs above it
+    pub fn remove_program(&mut self, name: &str) -> Result<(), EditError> {
+        self.above_bottom(name)?;
+
+        let below = self.detach(name)?;
+        for removed in self.top_down(name) {
+            self.programs_hm.remove(&removed);
+            self.holders.remove(&removed);
+            self.unbalanced.remove(&removed);
+        }
+        self.rebalance(&below);
+        Ok(())
+    }
+}
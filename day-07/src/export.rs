@@ -0,0 +1,113 @@
+//
+// The tower in forms other tools can read: a Graphviz digraph in which
+// each program points to those on its disc, and a JSON tree. Programs
+// whose weight unbalances their disc are highlighted in the graph and
+// given their corrected weight in the tree, as are those holding a disc
+// which no one weight would balance. Program names are lower case
+// letters, so need no escaping in either.
+//
+use {Correction, Imbalance, Tower};
+
+use std::io::{self, Write};
+
+// The correction to the weight of name, if it is the odd one out
+fn correction(imbalances: &[Imbalance], name: &str) -> Option<Option<u32>> {
+    imbalances.iter().find_map(|imbalance| match imbalance.correction {
+        Correction::Odd {
+            ref program,
+            corrected_weight,
+            ..
+        } if program == name => Some(corrected_weight),
+        _ => None,
+    })
+}
+
+// Whether name holds a disc which no one weight would balance
+fn ambiguous(imbalances: &[Imbalance], name: &str) -> bool {
+    imbalances
+        .iter()
+        .any(|imbalance| imbalance.holder == name && imbalance.correction == Correction::Ambiguous)
+}
+
+pub fn write_dot<W: Write>(tower: &Tower, mut output: W) -> io::Result<()> {
+    let imbalances = tower.imbalances();
+
+    writeln!(output, "digraph tower {{")?;
+    writeln!(output, "    node [shape=box];")?;
+    for name in tower.top_down(&tower.bottom) {
+        let program = &tower.programs_hm[&name];
+        write!(
+            output,
+            "    \"{}\" [label=\"{}\\nweight {}\\ntotal {}\"",
+            name,
+            name,
+            program.weight,
+            program.total_weight()
+        )?;
+        let xlabel = match correction(&imbalances, &name) {
+            Some(Some(corrected_weight)) => Some(format!("should weigh {}", corrected_weight)),
+            Some(None) => Some("cannot be corrected".to_string()),
+            None if ambiguous(&imbalances, &name) => Some("disc unbalanced".to_string()),
+            None => None,
+        };
+        if let Some(xlabel) = xlabel {
+            write!(
+                output,
+                ", xlabel=\"{}\", color=red, style=filled, fillcolor=mistyrose",
+                xlabel
+            )?;
+        }
+        writeln!(output, "];")?;
+    }
+    for name in tower.top_down(&tower.bottom) {
+        for subname in &tower.programs_hm[&name].disc {
+            writeln!(output, "    \"{}\" -> \"{}\";", name, subname)?;
+        }
+    }
+    writeln!(output, "}}")
+}
+
+fn write_json_program<W: Write>(
+    tower: &Tower,
+    imbalances: &[Imbalance],
+    name: &str,
+    depth: usize,
+    output: &mut W,
+) -> io::Result<()> {
+    let program = &tower.programs_hm[name];
+    let indent = "  ".repeat(depth);
+
+    writeln!(output, "{{")?;
+    writeln!(output, "{}  \"name\": \"{}\",", indent, name)?;
+    writeln!(output, "{}  \"weight\": {},", indent, program.weight)?;
+    writeln!(output, "{}  \"total_weight\": {},", indent, program.total_weight())?;
+    match correction(imbalances, name) {
+        Some(Some(corrected_weight)) => {
+            writeln!(output, "{}  \"corrected_weight\": {},", indent, corrected_weight)?
+        }
+        Some(None) => writeln!(output, "{}  \"corrected_weight\": null,", indent)?,
+        None => {}
+    }
+    if ambiguous(imbalances, name) {
+        writeln!(output, "{}  \"unbalanced_disc\": true,", indent)?;
+    }
+    write!(output, "{}  \"disc\": [", indent)?;
+    for (index, subname) in program.disc.iter().enumerate() {
+        if index > 0 {
+            write!(output, ",")?;
+        }
+        write!(output, "\n{}    ", indent)?;
+        write_json_program(tower, imbalances, subname, depth + 2, output)?;
+    }
+    if !program.disc.is_empty() {
+        write!(output, "\n{}  ", indent)?;
+    }
+    writeln!(output, "]")?;
+    write!(output, "{}}}", indent)
+}
+
+pub fn write_json<W: Write>(tower: &Tower, mut output: W) -> io::Result<()> {
+    let imbalances = tower.imbalances();
+    write_json_program(tower, &imbalances, &tower.bottom, 0, &mut output)?;
+    writeln!(output)
+}
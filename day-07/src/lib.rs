@@ -0,0 +1,676 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::collections::hash_map::Entry;
+
+extern crate pest;
+#[macro_use]
+extern crate pest_derive;
+extern crate solver;
+
+mod edit;
+mod export;
+mod validate;
+
+use std::io::{self, Write};
+
+use pest::Parser;
+use solver::{Answer, ParseError, SolveError, Solver};
+pub use edit::EditError;
+pub use validate::TowerError;
+use validate::validate;
+
+#[derive(Parser)]
+#[grammar = "program.pest"]
+struct ProgramParser;
+
+#[derive(Debug, Default)]
+struct Program {
+    name: String,
+    weight: u32,
+    disc: Vec<String>,
+    disc_weight: Option<u32>,
+}
+
+impl Program {
+    fn checked_total_weight(&self) -> Option<u32> {
+        self.weight.checked_add(self.disc_weight.unwrap_or_default())
+    }
+
+    // A tower whose total weight would not fit in a u32 is refused when
+    // parsed or edited, so neither can that of any program in it
+    fn total_weight(&self) -> u32 {
+        self.checked_total_weight().expect("total weight kept within a u32")
+    }
+}
+
+// How a disc which does not balance might be made to
+#[derive(Debug, Clone, PartialEq)]
+pub enum Correction {
+    // Only program differs from the rest, and would match them at the
+    // corrected weight; there is none if its disc alone outweighs them
+    Odd {
+        program: String,
+        current_weight: u32,
+        corrected_weight: Option<u32>,
+    },
+    // No one program differs from all of the others: the disc holds
+    // just two, or more than two different total weights
+    Ambiguous,
+}
+
+// The disc of holder, whose programs do not all weigh the same
+#[derive(Debug, Clone, PartialEq)]
+pub struct Imbalance {
+    pub holder: String,
+    pub correction: Correction,
+}
+
+struct Tower {
+    bottom: String,
+    programs_hm: HashMap<String, Program>,
+    // The program holding each one but the bottom on its disc
+    holders: HashMap<String, String>,
+    // The imbalance on the disc of each program whose disc has one
+    unbalanced: BTreeMap<String, Imbalance>,
+}
+
+impl Tower {
+    fn new(programs: Vec<Program>) -> Result<Tower, TowerError> {
+        let bottom = validate(&programs)?;
+
+        let mut programs_hm: HashMap<String, Program> = HashMap::new();
+        let mut holders: HashMap<String, String> = HashMap::new();
+        for program in programs {
+            for subname in &program.disc {
+                holders.insert(subname.clone(), program.name.clone());
+            }
+            programs_hm.insert(program.name.clone(), program);
+        }
+
+        Ok(Tower {
+            bottom,
+            programs_hm,
+            holders,
+            unbalanced: BTreeMap::new(),
+        })
+    }
+
+    fn calculate_disc_weights(&mut self) -> Result<(), TowerError> {
+        let mut stack: Vec<String> = Vec::new();
+
+        stack.push(self.bottom.clone());
+
+        loop {
+            if stack.is_empty() {
+                break;
+            }
+
+            let name = stack.pop().unwrap();
+
+            let mut program = Program::default();
+            if let Some(p) = self.programs_hm.get(&name) {
+                program.name = p.name.clone();
+                program.weight = p.weight;
+                program.disc = p.disc.clone();
+            }
+
+            if program.disc.is_empty() {
+                if let Some(p) = self.programs_hm.get_mut(&name) {
+                    p.disc_weight = Some(0);
+                    continue;
+                }
+            }
+
+            let mut needs_calculation = Vec::new();
+            for subname in program.disc.clone() {
+                if let Some(subprogram) = self.programs_hm.get(&subname) {
+                    if subprogram.disc_weight.is_none() {
+                        needs_calculation.push(subname);
+                    }
+                }
+            }
+            if !needs_calculation.is_empty() {
+                stack.push(name);
+                for subname in needs_calculation {
+                    stack.push(subname);
+                }
+                continue;
+            }
+
+            let mut disc_weight: u32 = 0;
+            for subname in program.disc.clone() {
+                if let Some(subprogram) = self.programs_hm.get(&subname) {
+                    disc_weight = subprogram
+                        .checked_total_weight()
+                        .and_then(|subweight| disc_weight.checked_add(subweight))
+                        .ok_or_else(|| TowerError::Overflow(name.clone()))?;
+                }
+            }
+            if let Some(p) = self.programs_hm.get_mut(&name) {
+                p.disc_weight = Some(disc_weight);
+                if p.checked_total_weight().is_none() {
+                    return Err(TowerError::Overflow(name));
+                }
+            }
+        }
+
+        let names: Vec<String> = self.programs_hm.keys().cloned().collect();
+        self.rebalance(&names);
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    fn display(&self) {
+        let mut stack: Vec<(String, u32)> = Vec::new();
+
+        stack.push((self.bottom.clone(), 0));
+
+        loop {
+            if stack.is_empty() {
+                break;
+            }
+
+            let (name, depth) = stack.pop().unwrap();
+
+            let mut program = Program::default();
+            if let Some(p) = self.programs_hm.get(&name) {
+                program.name = p.name.clone();
+                program.weight = p.weight;
+                program.disc = p.disc.clone();
+                program.disc_weight = p.disc_weight;
+            }
+
+            for _ in 0..depth {
+                program.name.insert(0, '-');
+            }
+            print!(
+                "{:12} ({:6}) [{:6}]",
+                program.name,
+                program.weight,
+                program.disc_weight.unwrap()
+            );
+
+            for subprogram in program.disc.clone() {
+                print!(" {}", subprogram);
+            }
+            println!();
+
+            program.disc.reverse();
+            for subprogram in program.disc {
+                stack.push((subprogram, depth + 1));
+            }
+        }
+    }
+
+    // Every program from name up, each before all of those on its disc
+    fn top_down(&self, name: &str) -> Vec<String> {
+        let mut order = Vec::new();
+        let mut stack = vec![name.to_string()];
+
+        while let Some(name) = stack.pop() {
+            if let Some(program) = self.programs_hm.get(&name) {
+                for subname in program.disc.iter().rev() {
+                    stack.push(subname.clone());
+                }
+            }
+            order.push(name);
+        }
+
+        order
+    }
+
+    //
+    // Whether the disc of name balances, and if not, which program on
+    // it differs from the rest and the weight it would need to match
+    // them
+    //
+    fn unbalanced_on(&self, name: &str) -> Option<Imbalance> {
+        let mut weights_hm: HashMap<u32, Vec<&Program>> = HashMap::new();
+        for subname in &self.programs_hm[name].disc {
+            let subprogram = &self.programs_hm[subname];
+            match weights_hm.entry(subprogram.total_weight()) {
+                Entry::Vacant(vacant) => {
+                    vacant.insert(vec![subprogram]);
+                }
+                Entry::Occupied(mut occupied) => {
+                    occupied.get_mut().push(subprogram);
+                }
+            };
+        }
+
+        if weights_hm.len() < 2 {
+            return None;
+        }
+        let standard = weights_hm.iter().find(|&(_, v)| v.len() > 1);
+        let odd = weights_hm.iter().find(|&(_, v)| v.len() == 1);
+        let correction = match (weights_hm.len(), standard, odd) {
+            (2, Some((&standard, _)), Some((&total, odd))) => {
+                let program = odd[0];
+                let corrected_weight = if standard >= total {
+                    program.weight.checked_add(standard - total)
+                } else {
+                    program.weight.checked_sub(total - standard)
+                };
+                Correction::Odd {
+                    program: program.name.clone(),
+                    current_weight: program.weight,
+                    corrected_weight,
+                }
+            }
+            _ => Correction::Ambiguous,
+        };
+
+        Some(Imbalance {
+            holder: name.to_string(),
+            correction,
+        })
+    }
+
+    // Look again at whether the disc of each program balances
+    fn rebalance(&mut self, names: &[String]) {
+        for name in names {
+            match self.unbalanced_on(name) {
+                Some(imbalance) => {
+                    self.unbalanced.insert(name.clone(), imbalance);
+                }
+                None => {
+                    self.unbalanced.remove(name);
+                }
+            }
+        }
+    }
+
+    // The programs below name, from its holder down to the bottom
+    fn below(&self, name: &str) -> Vec<String> {
+        let mut below = Vec::new();
+        let mut name = name;
+        while let Some(holder) = self.holders.get(name) {
+            below.push(holder.clone());
+            name = holder;
+        }
+        below
+    }
+
+    //
+    // Every disc whose programs are not balanced, deepest first. A
+    // program of the wrong weight also unbalances each disc below it,
+    // so the first is the one to correct.
+    //
+    fn imbalances(&self) -> Vec<Imbalance> {
+        let mut unbalanced: Vec<(usize, &Imbalance)> = self.unbalanced
+            .iter()
+            .map(|(name, imbalance)| (self.below(name).len(), imbalance))
+            .collect();
+        unbalanced.sort_by_key(|&(depth, _)| Reverse(depth));
+        unbalanced
+            .into_iter()
+            .map(|(_, imbalance)| imbalance.clone())
+            .collect()
+    }
+
+    fn find_imbalance(&self) -> Option<Imbalance> {
+        self.imbalances().into_iter().next()
+    }
+}
+
+fn parse_line(line: &str) -> Result<Program, ParseError> {
+    let mut program = Program::default();
+
+    let pairs = ProgramParser::parse(Rule::line, line).map_err(ParseError::from_pest)?;
+
+    for pair in pairs {
+        let rule = pair.as_rule();
+        let span = pair.clone().into_span();
+        let text = span.as_str().to_string();
+        match rule {
+            Rule::name => {
+                program.name = text;
+            }
+            Rule::weight => {
+                program.weight = text.parse()
+                    .map_err(|e| ParseError::token(1, line, span.as_str(), e))?;
+            }
+            Rule::subprogram => {
+                program.disc.push(text);
+            }
+            _ => {
+                println!("unknown rule {:?}", rule);
+            }
+        }
+    }
+
+    Ok(program)
+}
+
+pub struct Puzzle {
+    tower: Tower,
+}
+
+impl Puzzle {
+    pub fn imbalances(&self) -> Vec<Imbalance> {
+        self.tower.imbalances()
+    }
+
+    // The weight of name with all the programs above it
+    pub fn total_weight(&self, name: &str) -> Option<u32> {
+        self.tower
+            .programs_hm
+            .get(name)
+            .map(|program| program.total_weight())
+    }
+
+    pub fn set_weight(&mut self, name: &str, weight: u32) -> Result<(), EditError> {
+        self.tower.set_weight(name, weight)
+    }
+
+    pub fn move_program(&mut self, name: &str, holder: &str) -> Result<(), EditError> {
+        self.tower.move_program(name, holder)
+    }
+
+    pub fn add_program(&mut self, name: &str, weight: u32, holder: &str) -> Result<(), EditError> {
+        self.tower.add_program(name, weight, holder)
+    }
+
+    pub fn remove_program(&mut self, name: &str) -> Result<(), EditError> {
+        self.tower.remove_program(name)
+    }
+
+    pub fn write_dot<W: Write>(&self, output: W) -> io::Result<()> {
+        export::write_dot(&self.tower, output)
+    }
+
+    pub fn write_json<W: Write>(&self, output: W) -> io::Result<()> {
+        export::write_json(&self.tower, output)
+    }
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let programs: Vec<Program> = solver::parse_lines(input, parse_line)?;
+
+        let lines: Vec<&str> = input.lines().collect();
+        let names: Vec<String> = programs.iter().map(|program| program.name.clone()).collect();
+        let tower = Tower::new(programs)
+            .and_then(|mut tower| {
+                tower.calculate_disc_weights()?;
+                Ok(tower)
+            })
+            .map_err(|e| {
+                let index = e.program()
+                    .and_then(|program| names.iter().rposition(|name| name == program))
+                    .unwrap_or(0);
+                ParseError::new(index + 1, 1, lines.get(index).unwrap_or(&""), e)
+            })?;
+
+        Ok(Puzzle { tower })
+    }
+
+    fn part1(&self) -> Answer {
+        Ok(Some(self.tower.bottom.clone()))
+    }
+
+    fn part2(&self) -> Answer {
+        let imbalance = match self.tower.find_imbalance() {
+            Some(imbalance) => imbalance,
+            None => return Ok(None),
+        };
+        match imbalance.correction {
+            Correction::Odd {
+                corrected_weight: Some(corrected_weight),
+                ..
+            } => Ok(Some(corrected_weight.to_string())),
+            _ => Err(SolveError::new(format!(
+                "no one weight balances the disc of {}",
+                imbalance.holder
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST: &str = include_str!("../test.txt");
+
+    fn odd(holder: &str, program: &str, current_weight: u32, corrected_weight: u32) -> Imbalance {
+        Imbalance {
+            holder: holder.to_string(),
+            correction: Correction::Odd {
+                program: program.to_string(),
+                current_weight,
+                corrected_weight: Some(corrected_weight),
+            },
+        }
+    }
+
+    #[test]
+    fn provided_example() {
+        let puzzle = Puzzle::parse(TEST).unwrap();
+        assert_eq!(Ok(Some("tknk".to_string())), puzzle.part1());
+        assert_eq!(Ok(Some("60".to_string())), puzzle.part2());
+        assert_eq!(Some(odd("tknk", "ugml", 68, 60)), puzzle.tower.find_imbalance());
+    }
+
+    #[test]
+    fn imbalances_bottom_up() {
+        // Make ebii too heavy as well, unbalancing ugml's disc
+        let input = TEST.replace("ebii (61)", "ebii (64)");
+        let puzzle = Puzzle::parse(&input).unwrap();
+        assert_eq!(
+            vec![odd("ugml", "ebii", 64, 61), odd("tknk", "ugml", 68, 57)],
+            puzzle.imbalances()
+        );
+
+        let balanced = TEST.replace("ugml (68)", "ugml (60)");
+        assert!(Puzzle::parse(&balanced).unwrap().imbalances().is_empty());
+        assert_eq!(Ok(None), Puzzle::parse(&balanced).unwrap().part2());
+    }
+
+    #[test]
+    fn imbalances_without_correction() {
+        let puzzle = Puzzle::parse("a (1) -> b, c\nb (2)\nc (3)").unwrap();
+        assert_eq!(
+            vec![Imbalance {
+                holder: "a".to_string(),
+                correction: Correction::Ambiguous,
+            }],
+            puzzle.imbalances()
+        );
+        assert_eq!(
+            Err(SolveError::new("no one weight balances the disc of a")),
+            puzzle.part2()
+        );
+
+        // d would need to weigh -5 to match b and c
+        let puzzle = Puzzle::parse("a (1) -> b, c, d\nb (5)\nc (5)\nd (1) -> e\ne (10)").unwrap();
+        assert_eq!(
+            vec![Imbalance {
+                holder: "a".to_string(),
+                correction: Correction::Odd {
+                    program: "d".to_string(),
+                    current_weight: 1,
+                    corrected_weight: None,
+                },
+            }],
+            puzzle.imbalances()
+        );
+        assert!(puzzle.part2().is_err());
+    }
+
+    #[test]
+    fn trailing_text() {
+        let e = Puzzle::parse("pbga (66)\nxhth (57) xyz\n").err().unwrap();
+        assert_eq!(2, e.line);
+        assert_eq!("xhth (57) xyz", e.text);
+        assert!(Puzzle::parse("fwft (72) -> ktlj,\nktlj (57)\n").is_err());
+    }
+
+    fn tower_error(input: &str) -> TowerError {
+        let programs: Vec<Program> = solver::parse_lines(input, parse_line).unwrap();
+        Tower::new(programs).err().unwrap()
+    }
+
+    #[test]
+    fn invalid_towers() {
+        assert_eq!(
+            TowerError::Duplicate("a".to_string()),
+            tower_error("a (1) -> b\nb (2)\na (3)")
+        );
+        assert_eq!(
+            TowerError::Undefined {
+                program: "a".to_string(),
+                subprogram: "c".to_string(),
+            },
+            tower_error("a (1) -> b, c\nb (2)")
+        );
+        assert_eq!(
+            TowerError::Shared {
+                subprogram: "c".to_string(),
+                holders: ("a".to_string(), "b".to_string()),
+            },
+            tower_error("a (1) -> b, c\nb (2) -> c\nc (3)")
+        );
+        assert_eq!(
+            TowerError::MultipleBottoms(vec!["a".to_string(), "c".to_string()]),
+            tower_error("a (1) -> b\nb (2)\nc (3)")
+        );
+        assert_eq!(
+            TowerError::Cycle(vec!["c".to_string(), "d".to_string(), "b".to_string()]),
+            tower_error("a (1)\nb (2) -> c\nc (3) -> d\nd (4) -> b")
+        );
+        assert_eq!(TowerError::Cycle(vec!["a".to_string()]), tower_error("a (1) -> a"));
+        assert_eq!(TowerError::NoBottom, tower_error(""));
+
+        let e = Puzzle::parse("a (1) -> b\nb (2)\nc (3)").err().unwrap();
+        assert_eq!(3, e.line);
+        assert_eq!("more than one program is at the bottom: a, c", e.message);
+
+        let e = Puzzle::parse("b (4294967295)\na (1) -> b").err().unwrap();
+        assert_eq!(2, e.line);
+        assert_eq!("the total weight of a is too large", e.message);
+    }
+
+    #[test]
+    fn export_dot() {
+        let mut dot = Vec::new();
+        Puzzle::parse(TEST).unwrap().write_dot(&mut dot).unwrap();
+        let dot = String::from_utf8(dot).unwrap();
+        let lines: Vec<&str> = dot.lines().collect();
+        assert_eq!(2 + 13 + 12 + 1, lines.len());
+        assert_eq!("    \"tknk\" [label=\"tknk\\nweight 41\\ntotal 778\"];", lines[2]);
+        assert_eq!(
+            "    \"ugml\" [label=\"ugml\\nweight 68\\ntotal 251\", xlabel=\"should weigh 60\", color=red, style=filled, fillcolor=mistyrose];",
+            lines[3]
+        );
+        assert_eq!("    \"tknk\" -> \"ugml\";", lines[15]);
+        assert_eq!("}", lines[27]);
+    }
+
+    #[test]
+    fn export_json() {
+        let mut json = Vec::new();
+        Puzzle::parse("a (1) -> b, c\nb (2)\nc (3)").unwrap().write_json(&mut json).unwrap();
+        assert_eq!(
+            "{
+  \"name\": \"a\",
+  \"weight\": 1,
+  \"total_weight\": 6,
+  \"unbalanced_disc\": true,
+  \"disc\": [
+    {
+      \"name\": \"b\",
+      \"weight\": 2,
+      \"total_weight\": 2,
+      \"disc\": []
+    },
+    {
+      \"name\": \"c\",
+      \"weight\": 3,
+      \"total_weight\": 3,
+      \"disc\": []
+    }
+  ]
+}
+",
+            String::from_utf8(json).unwrap()
+        );
+    }
+
+    #[test]
+    fn edit_tower() {
+        let mut puzzle = Puzzle::parse(TEST).unwrap();
+
+        puzzle.set_weight("ugml", 60).unwrap();
+        assert_eq!(Some(243), puzzle.total_weight("ugml"));
+        assert_eq!(Some(770), puzzle.total_weight("tknk"));
+        assert!(puzzle.imbalances().is_empty());
+
+        puzzle.move_program("gyxo", "pbga").unwrap();
+        assert_eq!(Some(182), puzzle.total_weight("ugml"));
+        assert_eq!(Some(304), puzzle.total_weight("padx"));
+        assert_eq!(Some(770), puzzle.total_weight("tknk"));
+        // Three different weights on the bottom disc leave it unclear
+        // which is wrong
+        assert_eq!(
+            vec![
+                odd("padx", "pbga", 66, 5),
+                Imbalance {
+                    holder: "tknk".to_string(),
+                    correction: Correction::Ambiguous,
+                },
+            ],
+            puzzle.imbalances()
+        );
+
+        puzzle.remove_program("pbga").unwrap();
+        assert_eq!(None, puzzle.total_weight("gyxo"));
+        assert_eq!(Some(177), puzzle.total_weight("padx"));
+        assert_eq!(Some(643), puzzle.total_weight("tknk"));
+
+        puzzle.add_program("pbga", 66, "padx").unwrap();
+        puzzle.add_program("gyxo", 61, "ugml").unwrap();
+        assert_eq!(Some(778 - 8), puzzle.total_weight("tknk"));
+        assert!(puzzle.imbalances().is_empty());
+        assert_eq!(Ok(None), puzzle.part2());
+
+        puzzle.set_weight("ktlj", 60).unwrap();
+        assert_eq!(
+            vec![odd("fwft", "ktlj", 60, 57), odd("tknk", "fwft", 72, 69)],
+            puzzle.imbalances()
+        );
+    }
+
+    #[test]
+    fn invalid_edits() {
+        let mut puzzle = Puzzle::parse(TEST).unwrap();
+        assert_eq!(Err(EditError::Unknown("abc".to_string())), puzzle.set_weight("abc", 1));
+        assert_eq!(Err(EditError::Bottom("tknk".to_string())), puzzle.remove_program("tknk"));
+        assert_eq!(
+            Err(EditError::Cycle {
+                program: "ugml".to_string(),
+                holder: "gyxo".to_string(),
+            }),
+            puzzle.move_program("ugml", "gyxo")
+        );
+        assert_eq!(Err(EditError::Duplicate("padx".to_string())), puzzle.add_program("padx", 1, "tknk"));
+        assert_eq!(Err(EditError::InvalidName("Ab".to_string())), puzzle.add_program("Ab", 1, "tknk"));
+        assert_eq!(Some(778), puzzle.total_weight("tknk"));
+
+        // Each would take a total weight beyond a u32, so changes nothing
+        assert_eq!(Err(EditError::Overflow("ugml".to_string())), puzzle.set_weight("ugml", u32::MAX));
+        assert_eq!(
+            Err(EditError::Overflow("ugml".to_string())),
+            puzzle.set_weight("gyxo", u32::MAX - 100)
+        );
+        assert_eq!(
+            Err(EditError::Overflow("tknk".to_string())),
+            puzzle.add_program("big", u32::MAX - 700, "pbga")
+        );
+        assert_eq!(None, puzzle.total_weight("big"));
+        assert_eq!(Some(61), puzzle.total_weight("gyxo"));
+        assert_eq!(Some(778), puzzle.total_weight("tknk"));
+        assert!(puzzle.move_program("pbga", "ugml").is_ok());
+        assert_eq!(Some(778), puzzle.total_weight("tknk"));
+    }
+}
@@ -0,0 +1,128 @@
+use Program;
+
+use std::collections::{HashMap, HashSet};
+use std::error;
+use std::fmt::{self, Display, Formatter};
+
+// Why the programs do not make up a single tower
+#[derive(Debug, Clone, PartialEq)]
+pub enum TowerError {
+    Duplicate(String),
+    Undefined { program: String, subprogram: String },
+    Shared { subprogram: String, holders: (String, String) },
+    // There are no programs at all
+    NoBottom,
+    MultipleBottoms(Vec<String>),
+    // Each program holds the next on its disc, and the last holds the
+    // first
+    Cycle(Vec<String>),
+    // The total weight of the program does not fit in a u32
+    Overflow(String),
+}
+
+impl TowerError {
+    // The program whose line the error is reported at
+    pub fn program(&self) -> Option<&str> {
+        match *self {
+            TowerError::Duplicate(ref program) => Some(program),
+            TowerError::Undefined { ref program, .. } => Some(program),
+            TowerError::Shared { ref holders, .. } => Some(&holders.1),
+            TowerError::NoBottom => None,
+            TowerError::MultipleBottoms(ref bottoms) => Some(&bottoms[1]),
+            TowerError::Cycle(ref programs) => Some(&programs[0]),
+            TowerError::Overflow(ref program) => Some(program),
+        }
+    }
+}
+
+impl Display for TowerError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            TowerError::Duplicate(ref program) => write!(f, "{} is defined more than once", program),
+            TowerError::Undefined {
+                ref program,
+                ref subprogram,
+            } => write!(f, "{} holds {}, which is not defined", program, subprogram),
+            TowerError::Shared {
+                ref subprogram,
+                ref holders,
+            } => write!(
+                f,
+                "{} is held by both {} and {}",
+                subprogram, holders.0, holders.1
+            ),
+            TowerError::NoBottom => write!(f, "no program is at the bottom"),
+            TowerError::MultipleBottoms(ref bottoms) => {
+                write!(f, "more than one program is at the bottom: {}", bottoms.join(", "))
+            }
+            TowerError::Cycle(ref programs) => write!(
+                f,
+                "programs hold each other in a cycle: {} -> {}",
+                programs.join(" -> "),
+                programs[0]
+            ),
+            TowerError::Overflow(ref program) => {
+                write!(f, "the total weight of {} is too large", program)
+            }
+        }
+    }
+}
+
+impl error::Error for TowerError {}
+
+//
+// Check that the programs make up a single tower, returning the name
+// of the program at the bottom. Each program must be defined once and
+// every program but the bottom one must stand on exactly one disc.
+//
+pub fn validate(programs: &[Program]) -> Result<String, TowerError> {
+    let mut defined = HashSet::new();
+    for program in programs {
+        if !defined.insert(program.name.as_str()) {
+            return Err(TowerError::Duplicate(program.name.clone()));
+        }
+    }
+
+    let mut holders: HashMap<&str, &str> = HashMap::new();
+    for program in programs {
+        for subprogram in &program.disc {
+            if !defined.contains(subprogram.as_str()) {
+                return Err(TowerError::Undefined {
+                    program: program.name.clone(),
+                    subprogram: subprogram.clone(),
+                });
+            }
+            if let Some(holder) = holders.insert(subprogram, &program.name) {
+                return Err(TowerError::Shared {
+                    subprogram: subprogram.clone(),
+                    holders: (holder.to_string(), program.name.clone()),
+                });
+            }
+        }
+    }
+
+    // With one holder each, a program either leads down to a bottom
+    // or round a cycle
+    for program in programs {
+        let mut path: Vec<&str> = vec![&program.name];
+        while let Some(&holder) = holders.get(path[path.len() - 1]) {
+            if let Some(start) = path.iter().position(|&name| name == holder) {
+                let mut cycle: Vec<String> = path[start..].iter().map(|name| name.to_string()).collect();
+                cycle.reverse();
+                return Err(TowerError::Cycle(cycle));
+            }
+            path.push(holder);
+        }
+    }
+
+    let bottoms: Vec<String> = programs
+        .iter()
+        .filter(|program| !holders.contains_key(program.name.as_str()))
+        .map(|program| program.name.clone())
+        .collect();
+    match bottoms.len() {
+        0 => Err(TowerError::NoBottom),
+        1 => Ok(bottoms[0].clone()),
+        _ => Err(TowerError::MultipleBottoms(bottoms)),
+    }
+}
@@ -0,0 +1,61 @@
+//
+// A log of the instructions carried out, for asking how the registers
+// changed along the way. Every register starts at 0.
+//
+
+// One instruction: the register it would write, and its value before
+// and after. When the condition did not fire, old and new are equal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub index: usize,
+    pub register: String,
+    pub old: i32,
+    pub new: i32,
+    pub fired: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct History {
+    entries: Vec<Entry>,
+}
+
+impl History {
+    pub fn new() -> History {
+        History::default()
+    }
+
+    pub fn record(&mut self, entry: Entry) {
+        self.entries.push(entry);
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    fn writes<'a>(&'a self, register: &'a str) -> impl Iterator<Item = &'a Entry> + 'a {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.fired && entry.register == register)
+    }
+
+    // The value of register once instruction index has been carried out
+    pub fn value_after(&self, register: &str, index: usize) -> i32 {
+        self.writes(register)
+            .take_while(|entry| entry.index <= index)
+            .last()
+            .map_or(0, |entry| entry.new)
+    }
+
+    // The first instruction to leave register holding more than limit
+    pub fn first_exceeding(&self, register: &str, limit: i32) -> Option<usize> {
+        self.writes(register)
+            .find(|entry| entry.new > limit)
+            .map(|entry| entry.index)
+    }
+
+    // The least and greatest values register ever held
+    pub fn range(&self, register: &str) -> (i32, i32) {
+        self.writes(register)
+            .fold((0, 0), |(min, max), entry| (min.min(entry.new), max.max(entry.new)))
+    }
+}
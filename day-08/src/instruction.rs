@@ -0,0 +1,65 @@
+//
+// The register language, as a typed syntax tree. A line such as
+//
+//     c mul b if a > 1 and not b == c
+//
+// becomes an Instruction which applies Operation::Mul to register c
+// with the value of register b, provided the Condition holds.
+//
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    Register(String),
+    Number(i32),
+}
+
+// What an instruction does to its register
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Operation {
+    Inc,
+    Dec,
+    Mul,
+    Div,
+    Mod,
+    Set,
+}
+
+// Relational Operator
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RelOp {
+    Eq,
+    Neq,
+    Gte,
+    Lte,
+    Gt,
+    Lt,
+}
+
+impl RelOp {
+    pub fn holds(self, x: i32, y: i32) -> bool {
+        match self {
+            RelOp::Eq => x == y,
+            RelOp::Neq => x != y,
+            RelOp::Gte => x >= y,
+            RelOp::Lte => x <= y,
+            RelOp::Gt => x > y,
+            RelOp::Lt => x < y,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    Compare(Operand, RelOp, Operand),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+    Not(Box<Condition>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Instruction {
+    pub register: String,
+    pub operation: Operation,
+    pub operand: Operand,
+    pub condition: Condition,
+}
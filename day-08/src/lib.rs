@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+
+extern crate pest;
+#[macro_use]
+extern crate pest_derive;
+extern crate solver;
+
+mod history;
+mod instruction;
+mod parsing;
+
+pub use history::{Entry, History};
+
+pub use instruction::{Condition, Instruction, Operand, Operation, RelOp};
+pub use parsing::parse_line;
+use solver::{Answer, ParseError, SolveError, Solver};
+
+// An instruction which could not be carried out
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl Display for RunError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    registers: HashMap<String, i32>,
+    // The highest value written to any register, if any was
+    max_held: Option<i32>,
+    log: Option<History>,
+}
+
+impl State {
+    fn new(instructions: &[Instruction]) -> State {
+        let mut registers = HashMap::new();
+
+        for instr in instructions {
+            registers.entry(instr.register.clone()).or_insert(0);
+        }
+
+        State {
+            registers,
+            max_held: None,
+            log: None,
+        }
+    }
+
+    // Record every instruction from here on
+    fn enable_log(&mut self) {
+        self.log = Some(History::new());
+    }
+
+    // A register which is never modified holds 0
+    fn value(&self, operand: &Operand) -> i32 {
+        match *operand {
+            Operand::Register(ref register) => *self.registers.get(register).unwrap_or(&0),
+            Operand::Number(number) => number,
+        }
+    }
+
+    fn holds(&self, condition: &Condition) -> bool {
+        match *condition {
+            Condition::Compare(ref x, relop, ref y) => relop.holds(self.value(x), self.value(y)),
+            Condition::And(ref x, ref y) => self.holds(x) && self.holds(y),
+            Condition::Or(ref x, ref y) => self.holds(x) || self.holds(y),
+            Condition::Not(ref x) => !self.holds(x),
+        }
+    }
+
+    fn log(&mut self, index: usize, instr: &Instruction, new: i32, fired: bool) {
+        let old = self.registers[&instr.register];
+        if let Some(ref mut log) = self.log {
+            log.record(Entry {
+                index,
+                register: instr.register.clone(),
+                old,
+                new,
+                fired,
+            });
+        }
+    }
+
+    fn process_instruction(&mut self, index: usize, instr: &Instruction) -> Result<(), String> {
+        let x = self.registers[&instr.register];
+        if !self.holds(&instr.condition) {
+            self.log(index, instr, x, false);
+            return Ok(());
+        }
+
+        let y = self.value(&instr.operand);
+        let result = match instr.operation {
+            Operation::Inc => x.checked_add(y),
+            Operation::Dec => x.checked_sub(y),
+            Operation::Mul => x.checked_mul(y),
+            Operation::Div | Operation::Mod if y == 0 => {
+                return Err("division by zero".to_string());
+            }
+            Operation::Div => x.checked_div(y),
+            Operation::Mod => x.checked_rem(y),
+            Operation::Set => Some(y),
+        };
+        let regval = result.ok_or_else(|| "arithmetic overflow".to_string())?;
+
+        self.log(index, instr, regval, true);
+        self.registers.insert(instr.register.clone(), regval);
+        self.max_held = Some(self.max_held.map_or(regval, |max| max.max(regval)));
+        Ok(())
+    }
+
+    fn maximum_value(&self) -> Option<i32> {
+        self.registers.values().max().cloned()
+    }
+
+    fn max_held(&self) -> Option<i32> {
+        self.max_held
+    }
+}
+
+pub struct Puzzle {
+    instructions: Vec<Instruction>,
+}
+
+impl Puzzle {
+    fn run(&self, logging: bool) -> Result<State, RunError> {
+        let mut state = State::new(&self.instructions);
+        if logging {
+            state.enable_log();
+        }
+
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            state
+                .process_instruction(index, instruction)
+                .map_err(|message| RunError {
+                    line: index + 1,
+                    message,
+                })?;
+        }
+
+        Ok(state)
+    }
+
+    // Run the program, recording each instruction, numbered from 0
+    pub fn history(&self) -> Result<History, RunError> {
+        Ok(self.run(true)?.log.unwrap())
+    }
+
+    fn answer<F: Fn(&State) -> Option<i32>>(&self, f: F) -> Answer {
+        self.run(false)
+            .map(|state| f(&state).map(|value| value.to_string()))
+            .map_err(SolveError::new)
+    }
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        Ok(Puzzle {
+            instructions: solver::parse_lines(input, parse_line)?,
+        })
+    }
+
+    fn part1(&self) -> Answer {
+        self.answer(State::maximum_value)
+    }
+
+    fn part2(&self) -> Answer {
+        self.answer(State::max_held)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST: &str = include_str!("../test.txt");
+
+    #[test]
+    fn provided_example() {
+        let puzzle = Puzzle::parse(TEST).unwrap();
+        assert_eq!(Ok(Some("1".to_string())), puzzle.part1());
+        assert_eq!(Ok(Some("10".to_string())), puzzle.part2());
+    }
+
+    #[test]
+    fn parse_current_format() {
+        let instruction = parse_line("c dec -10 if a >= 1").unwrap();
+        assert_eq!(
+            Instruction {
+                register: "c".to_string(),
+                operation: Operation::Dec,
+                operand: Operand::Number(-10),
+                condition: Condition::Compare(
+                    Operand::Register("a".to_string()),
+                    RelOp::Gte,
+                    Operand::Number(1)
+                ),
+            },
+            instruction
+        );
+    }
+
+    #[test]
+    fn parse_compound_condition() {
+        let register = |name: &str| Operand::Register(name.to_string());
+        let compare = |x: &str, y: i32| Box::new(Condition::Compare(register(x), RelOp::Gt, Operand::Number(y)));
+        let instruction = parse_line("ab mul cd if a > 1 or not b > 2 and (c > 3 or d > 4)").unwrap();
+        assert_eq!(Operation::Mul, instruction.operation);
+        assert_eq!(register("cd"), instruction.operand);
+        assert_eq!(
+            Condition::Or(
+                compare("a", 1),
+                Box::new(Condition::And(
+                    Box::new(Condition::Not(compare("b", 2))),
+                    Box::new(Condition::Or(compare("c", 3), compare("d", 4)))
+                ))
+            ),
+            instruction.condition
+        );
+        assert!(parse_line("a inc 1 if b > 1 and").is_err());
+        assert!(parse_line("a pow 2 if 1 < 2").is_err());
+    }
+
+    #[test]
+    fn extended_operations() {
+        let puzzle = Puzzle::parse(
+            "a set 7 if 1 == 1\nb set a if a != b\na mul b if not a < 7\nb div 2 if a > b and b > 0\na mod b if a == 49 or b == 0\n",
+        ).unwrap();
+        let state = puzzle.run(false).unwrap();
+        assert_eq!(1, state.registers["a"]);
+        assert_eq!(3, state.registers["b"]);
+        assert_eq!(Some(49), state.max_held);
+
+        let puzzle = Puzzle::parse("a inc 1 if a == 0\na div b if a > 0\n").unwrap();
+        assert_eq!(
+            RunError {
+                line: 2,
+                message: "division by zero".to_string(),
+            },
+            puzzle.run(false).unwrap_err()
+        );
+        assert_eq!(Err(SolveError::new("line 2: division by zero")), puzzle.part1());
+    }
+
+    #[test]
+    fn history_queries() {
+        let history = Puzzle::parse(TEST).unwrap().history().unwrap();
+        assert_eq!(4, history.entries().len());
+        assert_eq!(
+            Entry {
+                index: 2,
+                register: "c".to_string(),
+                old: 0,
+                new: 10,
+                fired: true,
+            },
+            history.entries()[2]
+        );
+        assert!(!history.entries()[0].fired);
+
+        assert_eq!(0, history.value_after("c", 1));
+        assert_eq!(10, history.value_after("c", 2));
+        assert_eq!(-10, history.value_after("c", 3));
+        assert_eq!(Some(2), history.first_exceeding("c", 5));
+        assert_eq!(None, history.first_exceeding("a", 1));
+        assert_eq!((-10, 10), history.range("c"));
+        assert_eq!((0, 0), history.range("b"));
+    }
+
+    #[test]
+    fn unknown_registers_hold_zero() {
+        let puzzle = Puzzle::parse("a inc 1 if z > 0\nb inc 2 if z == 0\nc inc 3 if z < y\n").unwrap();
+        let state = puzzle.run(false).unwrap();
+        assert_eq!(0, state.registers["a"]);
+        assert_eq!(2, state.registers["b"]);
+        assert_eq!(0, state.registers["c"]);
+    }
+
+    #[test]
+    fn negative_maximum_held() {
+        let puzzle = Puzzle::parse("a dec 5 if a == 0\nb dec 3 if b == 0\n").unwrap();
+        assert_eq!(Ok(Some("-3".to_string())), puzzle.part2());
+
+        let puzzle = Puzzle::parse("a inc 1 if a > 0\n").unwrap();
+        assert_eq!(Ok(None), puzzle.part2());
+    }
+}
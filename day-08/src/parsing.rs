@@ -0,0 +1,91 @@
+use instruction::{Condition, Instruction, Operand, Operation, RelOp};
+
+use pest::iterators::Pair;
+use pest::Parser;
+use solver::ParseError;
+
+#[derive(Parser)]
+#[grammar = "instruction.pest"]
+struct InstructionParser;
+
+// Numbers are matched by the grammar, but may still be too large to
+// hold
+fn operand(line: &str, pair: Pair<Rule>) -> Result<Operand, ParseError> {
+    let text = pair.clone().into_span().as_str();
+    match pair.as_rule() {
+        Rule::register => Ok(Operand::Register(text.to_string())),
+        Rule::number => text.parse()
+            .map(Operand::Number)
+            .map_err(|e| ParseError::token(1, line, text, e)),
+        rule => unreachable!("unexpected rule {:?}", rule),
+    }
+}
+
+fn relop(pair: Pair<Rule>) -> RelOp {
+    match pair.as_rule() {
+        Rule::eq => RelOp::Eq,
+        Rule::neq => RelOp::Neq,
+        Rule::gte => RelOp::Gte,
+        Rule::lte => RelOp::Lte,
+        Rule::gt => RelOp::Gt,
+        Rule::lt => RelOp::Lt,
+        rule => unreachable!("unexpected rule {:?}", rule),
+    }
+}
+
+fn operation(pair: Pair<Rule>) -> Operation {
+    match pair.as_rule() {
+        Rule::inc => Operation::Inc,
+        Rule::dec => Operation::Dec,
+        Rule::mul => Operation::Mul,
+        Rule::div => Operation::Div,
+        Rule::modulo => Operation::Mod,
+        Rule::set => Operation::Set,
+        rule => unreachable!("unexpected rule {:?}", rule),
+    }
+}
+
+fn condition(line: &str, pair: Pair<Rule>) -> Result<Condition, ParseError> {
+    let rule = pair.as_rule();
+    let mut inner = pair.into_inner();
+    match rule {
+        Rule::comparison => {
+            let x = operand(line, inner.next().unwrap())?;
+            let relop = relop(inner.next().unwrap());
+            let y = operand(line, inner.next().unwrap())?;
+            Ok(Condition::Compare(x, relop, y))
+        }
+        Rule::negation => Ok(Condition::Not(Box::new(condition(line, inner.next().unwrap())?))),
+        Rule::conjunction | Rule::disjunction => {
+            let mut result = condition(line, inner.next().unwrap())?;
+            for pair in inner {
+                let next = Box::new(condition(line, pair)?);
+                result = if rule == Rule::conjunction {
+                    Condition::And(Box::new(result), next)
+                } else {
+                    Condition::Or(Box::new(result), next)
+                };
+            }
+            Ok(result)
+        }
+        rule => unreachable!("unexpected rule {:?}", rule),
+    }
+}
+
+pub fn parse_line(line: &str) -> Result<Instruction, ParseError> {
+    let mut pairs = InstructionParser::parse(Rule::line, line).map_err(ParseError::from_pest)?;
+
+    // The grammar matches the register, operation, operand and
+    // condition in that order
+    let register = pairs.next().unwrap().into_span().as_str().to_string();
+    let operation = operation(pairs.next().unwrap());
+    let operand = operand(line, pairs.next().unwrap())?;
+    let condition = condition(line, pairs.next().unwrap())?;
+
+    Ok(Instruction {
+        register,
+        operation,
+        operand,
+        condition,
+    })
+}
@@ -0,0 +1,648 @@
+//
+// The stream is processed a character at a time by a state machine, so
+// it can be read from anywhere and be of any length: only counts are
+// kept, unless a tree of its groups is wanted. The bytes of each UTF-8
+// character are gathered as they are read, so that a ! cancels all of
+// it and it is counted once. Within garbage any byte may appear, and
+// one which does not belong to a UTF-8 character is taken as a
+// character by itself; outside garbage anything but braces, < and ! is
+// ignored. The same state machine can also write the stream back out,
+// with its garbage stripped or its cancelled characters resolved.
+//
+extern crate solver;
+
+mod printer;
+mod tree;
+
+use std::error;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, ErrorKind, Read, Write};
+
+use solver::{Answer, ParseError, Solver};
+pub use printer::{Garbage, Style};
+use printer::Printer;
+pub use tree::{GarbageSpan, Group};
+use tree::TreeBuilder;
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    GroupStart,
+    GroupEnd,
+    GarbageStart,
+    GarbageEnd,
+    Cancel,
+    Other,
+}
+
+impl Token {
+    fn from_character(character: &[u8]) -> Token {
+        match *character {
+            [b'{'] => Token::GroupStart,
+            [b'}'] => Token::GroupEnd,
+            [b'<'] => Token::GarbageStart,
+            [b'>'] => Token::GarbageEnd,
+            [b'!'] => Token::Cancel,
+            _ => Token::Other,
+        }
+    }
+}
+
+// How many bytes the UTF-8 character starting with byte has, taking a
+// byte which cannot start one to be a character by itself
+fn character_length(byte: u8) -> usize {
+    match byte {
+        0xc2..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf4 => 4,
+        _ => 1,
+    }
+}
+
+// Why a stream could not be processed. Offsets are in bytes from the
+// start of the stream.
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    // A } with no group open
+    UnopenedGroup { offset: usize },
+    // The stream ended at offset with depth groups still open
+    UnclosedGroups { offset: usize, depth: u32 },
+    // The stream ended within the garbage opened at offset
+    UnterminatedGarbage { offset: usize },
+}
+
+impl StreamError {
+    fn offset(&self) -> Option<usize> {
+        match *self {
+            StreamError::Io(_) => None,
+            StreamError::UnopenedGroup { offset }
+            | StreamError::UnclosedGroups { offset, .. }
+            | StreamError::UnterminatedGarbage { offset } => Some(offset),
+        }
+    }
+}
+
+impl Display for StreamError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            StreamError::Io(ref e) => write!(f, "{}", e),
+            StreamError::UnopenedGroup { offset } => {
+                write!(f, "byte {}: closes a group which was never opened", offset)
+            }
+            StreamError::UnclosedGroups { offset, depth } => write!(
+                f,
+                "byte {}: stream ends with {} group{} open",
+                offset,
+                depth,
+                if depth == 1 { "" } else { "s" }
+            ),
+            StreamError::UnterminatedGarbage { offset } => {
+                write!(f, "byte {}: garbage is never closed", offset)
+            }
+        }
+    }
+}
+
+impl error::Error for StreamError {}
+
+// The answers to both parts, with garbage counted in characters
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Totals {
+    pub score: u64,
+    pub garbage: u64,
+}
+
+#[derive(Debug)]
+struct State {
+    // The bytes read so far of a character, and where it started
+    character: [u8; 4],
+    character_read: usize,
+    character_start: usize,
+    cancel: bool,
+    garbage: bool,
+    // Where the garbage being passed through opened
+    garbage_start: usize,
+    depth: u32,
+    totals: Totals,
+    tree: Option<TreeBuilder>,
+    printer: Option<Printer>,
+}
+
+impl State {
+    fn new() -> State {
+        State {
+            character: [0; 4],
+            character_read: 0,
+            character_start: 0,
+            cancel: false,
+            garbage: false,
+            garbage_start: 0,
+            depth: 0,
+            totals: Totals::default(),
+            tree: None,
+            printer: None,
+        }
+    }
+
+    // Also build the groups of the stream as it is processed
+    fn with_tree() -> State {
+        State {
+            tree: Some(TreeBuilder::new()),
+            ..State::new()
+        }
+    }
+
+    // Also write the stream back out in style
+    fn with_printer(style: Style) -> State {
+        State {
+            printer: Some(Printer::new(style)),
+            ..State::new()
+        }
+    }
+
+    //
+    // Process the byte found at offset in the stream, once the rest of
+    // the character it starts has been read. A character cut short by
+    // a byte which does not continue it is processed as far as it got.
+    //
+    fn process(&mut self, byte: u8, offset: usize) -> Result<(), StreamError> {
+        if self.character_read > 0 {
+            if byte & 0xc0 == 0x80 {
+                self.character[self.character_read] = byte;
+                self.character_read += 1;
+                if self.character_read == character_length(self.character[0]) {
+                    self.end_character()?;
+                }
+                return Ok(());
+            }
+            self.end_character()?;
+        }
+
+        if character_length(byte) == 1 {
+            return self.process_character(&[byte], offset);
+        }
+        self.character[0] = byte;
+        self.character_read = 1;
+        self.character_start = offset;
+        Ok(())
+    }
+
+    // Process the character whose bytes have been read
+    fn end_character(&mut self) -> Result<(), StreamError> {
+        let (character, read) = (self.character, self.character_read);
+        self.character_read = 0;
+        self.process_character(&character[..read], self.character_start)
+    }
+
+    // Process the character found at offset in the stream
+    fn process_character(&mut self, character: &[u8], offset: usize) -> Result<(), StreamError> {
+        let token = Token::from_character(character);
+
+        if self.cancel {
+            self.cancel = false;
+            if let Some(ref mut tree) = self.tree {
+                tree.cancelled();
+            }
+            if let Some(ref mut printer) = self.printer {
+                if self.garbage {
+                    printer.garbage_cancel(character);
+                }
+            }
+            return Ok(());
+        }
+
+        if token == Token::Cancel {
+            self.cancel = true;
+            if let Some(ref mut printer) = self.printer {
+                if self.garbage {
+                    printer.garbage_cancel(character);
+                }
+            }
+            return Ok(());
+        }
+
+        if self.garbage {
+            if token == Token::GarbageEnd {
+                self.garbage = false;
+                if let Some(ref mut tree) = self.tree {
+                    tree.garbage_end(offset);
+                }
+                if let Some(ref mut printer) = self.printer {
+                    printer.garbage_end();
+                }
+            } else {
+                self.totals.garbage += 1;
+                if let Some(ref mut tree) = self.tree {
+                    tree.garbage_character();
+                }
+                if let Some(ref mut printer) = self.printer {
+                    printer.garbage_character(character);
+                }
+            }
+            return Ok(());
+        }
+
+        match token {
+            Token::GroupStart => {
+                self.depth += 1;
+                if let Some(ref mut tree) = self.tree {
+                    tree.group_start(offset);
+                }
+                if let Some(ref mut printer) = self.printer {
+                    printer.group_start();
+                }
+            }
+            Token::GroupEnd => {
+                if self.depth == 0 {
+                    return Err(StreamError::UnopenedGroup { offset });
+                }
+                self.totals.score += u64::from(self.depth);
+                self.depth -= 1;
+                if let Some(ref mut tree) = self.tree {
+                    tree.group_end(offset);
+                }
+                if let Some(ref mut printer) = self.printer {
+                    printer.group_end();
+                }
+            }
+            Token::GarbageStart => {
+                self.garbage = true;
+                self.garbage_start = offset;
+                if let Some(ref mut tree) = self.tree {
+                    tree.garbage_start(offset);
+                }
+                if let Some(ref mut printer) = self.printer {
+                    printer.garbage_start();
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    // Check that nothing is left open at the end of the stream
+    fn finish(&mut self, offset: usize) -> Result<(), StreamError> {
+        if self.character_read > 0 {
+            self.end_character()?;
+        }
+        if self.garbage {
+            return Err(StreamError::UnterminatedGarbage {
+                offset: self.garbage_start,
+            });
+        }
+        if self.depth > 0 {
+            return Err(StreamError::UnclosedGroups {
+                offset,
+                depth: self.depth,
+            });
+        }
+        Ok(())
+    }
+
+    fn total_score(&self) -> u64 {
+        self.totals.score
+    }
+
+    fn total_garbage(&self) -> u64 {
+        self.totals.garbage
+    }
+}
+
+// Pass the whole of input through the state machine, returning its length
+fn process_stream<R: Read>(input: R, state: &mut State) -> Result<usize, StreamError> {
+    process_stream_to(input, state, &mut io::sink())
+}
+
+// As process_stream, writing out anything printed as each piece of
+// input is processed
+fn process_stream_to<R: Read, W: Write>(
+    mut input: R,
+    state: &mut State,
+    output: &mut W,
+) -> Result<usize, StreamError> {
+    let mut buffer = [0; 64 * 1024];
+    let mut offset = 0;
+
+    loop {
+        let count = match input.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(count) => count,
+            Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(StreamError::Io(e)),
+        };
+        for &byte in &buffer[..count] {
+            state.process(byte, offset)?;
+            offset += 1;
+        }
+        if let Some(ref mut printer) = state.printer {
+            printer.flush(output).map_err(StreamError::Io)?;
+        }
+    }
+
+    state.finish(offset)?;
+    if let Some(ref mut printer) = state.printer {
+        printer.finish();
+        printer.flush(output).map_err(StreamError::Io)?;
+    }
+    Ok(offset)
+}
+
+// The score and garbage count of a stream, in constant memory
+pub fn scan<R: Read>(input: R) -> Result<Totals, StreamError> {
+    let mut state = State::new();
+    process_stream(input, &mut state)?;
+
+    Ok(state.totals)
+}
+
+// The groups of a stream
+pub fn parse_tree<R: Read>(input: R) -> Result<Group, StreamError> {
+    let mut state = State::with_tree();
+    let length = process_stream(input, &mut state)?;
+
+    Ok(state.tree.unwrap().finish(length))
+}
+
+// Write a stream back out in style. Output is written as the stream is
+// read, so a malformed stream is only reported once part of it has
+// been written.
+pub fn print<R: Read, W: Write>(input: R, mut output: W, style: Style) -> Result<(), StreamError> {
+    let mut state = State::with_printer(style);
+    process_stream_to(input, &mut state, &mut output)?;
+
+    Ok(())
+}
+
+pub struct Puzzle {
+    state: State,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let input = input.trim_end();
+        let mut state = State::new();
+        process_stream(input.as_bytes(), &mut state).map_err(|e| {
+            // The offset of a malformed stream is always at a character
+            // boundary
+            let offset = e.offset().unwrap_or(0);
+            let line = input[..offset].matches('\n').count() + 1;
+            let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+            let text = input[line_start..].lines().next().unwrap_or("");
+            let column = input[line_start..offset].chars().count() + 1;
+            ParseError::new(line, column, text, e)
+        })?;
+
+        Ok(Puzzle { state })
+    }
+
+    fn part1(&self) -> Answer {
+        Ok(Some(self.state.total_score().to_string()))
+    }
+
+    fn part2(&self) -> Answer {
+        Ok(Some(self.state.total_garbage().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example_1() {
+        let mut state = State::new();
+        process_stream("{}".as_bytes(), &mut state).unwrap();
+        assert_eq!(1, state.total_score());
+    }
+
+    #[test]
+    fn part1_example_2() {
+        let mut state = State::new();
+        process_stream("{{{}}}".as_bytes(), &mut state).unwrap();
+        assert_eq!(6, state.total_score());
+    }
+
+    #[test]
+    fn part1_example_3() {
+        let mut state = State::new();
+        process_stream("{{},{}}".as_bytes(), &mut state).unwrap();
+        assert_eq!(5, state.total_score());
+    }
+
+    #[test]
+    fn part1_example_4() {
+        let mut state = State::new();
+        process_stream("{{{},{},{{}}}}".as_bytes(), &mut state).unwrap();
+        assert_eq!(16, state.total_score());
+    }
+
+    #[test]
+    fn part1_example_5() {
+        let mut state = State::new();
+        process_stream("{<a>,<a>,<a>,<a>}".as_bytes(), &mut state).unwrap();
+        assert_eq!(1, state.total_score());
+    }
+
+    #[test]
+    fn part1_example_6() {
+        let mut state = State::new();
+        process_stream("{{<ab>},{<ab>},{<ab>},{<ab>}}".as_bytes(), &mut state).unwrap();
+        assert_eq!(9, state.total_score());
+    }
+
+    #[test]
+    fn part1_example_7() {
+        let mut state = State::new();
+        process_stream("{{<!!>},{<!!>},{<!!>},{<!!>}}".as_bytes(), &mut state).unwrap();
+        assert_eq!(9, state.total_score());
+    }
+
+    #[test]
+    fn part1_example_8() {
+        let mut state = State::new();
+        process_stream("{{<a!>},{<a!>},{<a!>},{<ab>}}".as_bytes(), &mut state).unwrap();
+        assert_eq!(3, state.total_score());
+    }
+
+    #[test]
+    fn part2_example_1() {
+        let mut state = State::new();
+        process_stream("<>".as_bytes(), &mut state).unwrap();
+        assert_eq!(0, state.total_garbage());
+    }
+
+    #[test]
+    fn part2_example_2() {
+        let mut state = State::new();
+        process_stream("<random characters>".as_bytes(), &mut state).unwrap();
+        assert_eq!(17, state.total_garbage());
+    }
+
+    #[test]
+    fn part2_example_3() {
+        let mut state = State::new();
+        process_stream("<<<<>".as_bytes(), &mut state).unwrap();
+        assert_eq!(3, state.total_garbage());
+    }
+
+    #[test]
+    fn part2_example_4() {
+        let mut state = State::new();
+        process_stream("<{!>}>".as_bytes(), &mut state).unwrap();
+        assert_eq!(2, state.total_garbage());
+    }
+
+    #[test]
+    fn part2_example_5() {
+        let mut state = State::new();
+        process_stream("<!!>".as_bytes(), &mut state).unwrap();
+        assert_eq!(0, state.total_garbage());
+    }
+
+    #[test]
+    fn part2_example_6() {
+        let mut state = State::new();
+        process_stream("<!!!>>".as_bytes(), &mut state).unwrap();
+        assert_eq!(0, state.total_garbage());
+    }
+
+    #[test]
+    fn part2_example_7() {
+        let mut state = State::new();
+        process_stream("<{o\"i!a,<}i<a>".as_bytes(), &mut state).unwrap();
+        assert_eq!(10, state.total_garbage());
+    }
+
+    #[test]
+    fn tree_of_groups() {
+        let root = parse_tree("{{<ab>},{<!!>,{}},<a!>>}\n".as_bytes()).unwrap();
+        assert_eq!((0, 25), (root.start, root.end));
+        assert_eq!(1, root.children.len());
+
+        let outer = &root.children[0];
+        assert_eq!((0, 23, 1), (outer.start, outer.end, outer.depth));
+        assert_eq!(2, outer.children.len());
+        assert_eq!(
+            vec![GarbageSpan {
+                start: 18,
+                end: 22,
+                characters: 1,
+                cancelled: 1,
+            }],
+            outer.garbage
+        );
+        assert_eq!(
+            GarbageSpan {
+                start: 9,
+                end: 12,
+                characters: 0,
+                cancelled: 1,
+            },
+            outer.children[1].garbage[0]
+        );
+
+        let deepest = root.deepest();
+        assert_eq!((14, 15, 3), (deepest.start, deepest.end, deepest.depth));
+        assert_eq!(2, root.largest_garbage().unwrap().characters);
+        assert_eq!(
+            vec![(1, 1), (2, 2), (3, 1)],
+            root.groups_per_depth().into_iter().collect::<Vec<_>>()
+        );
+        let score: u32 = root.groups().iter().map(|group| group.depth).sum();
+        assert_eq!(8, score);
+    }
+
+    #[test]
+    fn any_byte_in_garbage() {
+        let stream: &[u8] = b"{<ABC.\xff\xfe!>\x00>,{}}";
+        assert_eq!(
+            Totals {
+                score: 3,
+                garbage: 7,
+            },
+            scan(stream).unwrap()
+        );
+        // Garbage is counted in characters, and a ! cancels the whole
+        // of the one after it
+        let puzzle = Puzzle::parse("{<Ünïcödé>}").unwrap();
+        assert_eq!(Ok(Some("7".to_string())), puzzle.part2());
+        let puzzle = Puzzle::parse("{<!é>}").unwrap();
+        assert_eq!(Ok(Some("0".to_string())), puzzle.part2());
+        assert_eq!(
+            Totals {
+                score: 1,
+                garbage: 2,
+            },
+            scan("{<!€€\u{1f600}!\u{1f600}>}".as_bytes()).unwrap()
+        );
+
+        // A character cut short counts as one, as does a stray byte
+        let stream: &[u8] = b"{<\xe2\x82!\xe2\x82\xff>}";
+        assert_eq!(
+            Totals {
+                score: 1,
+                garbage: 2,
+            },
+            scan(stream).unwrap()
+        );
+    }
+
+    #[test]
+    fn malformed_streams() {
+        assert!(matches!(
+            scan("{}}".as_bytes()),
+            Err(StreamError::UnopenedGroup { offset: 2 })
+        ));
+        assert!(matches!(
+            scan("{{<>}".as_bytes()),
+            Err(StreamError::UnclosedGroups { offset: 5, depth: 1 })
+        ));
+        assert!(matches!(
+            scan("{<a>,<b!>}".as_bytes()),
+            Err(StreamError::UnterminatedGarbage { offset: 5 })
+        ));
+
+        let e = Puzzle::parse("{{},<ab}\n").err().unwrap();
+        assert_eq!((1, 5), (e.line, e.column));
+        assert_eq!("byte 4: garbage is never closed", e.message);
+    }
+
+    #[test]
+    fn long_stream() {
+        // A million groups of garbage, read a piece at a time
+        let stream = "{".to_string() + &"{<!!a>},".repeat(1_000_000) + "{}}";
+        let totals = scan(io::BufReader::with_capacity(1000, stream.as_bytes())).unwrap();
+        assert_eq!(1 + 2 * 1_000_001, totals.score);
+        assert_eq!(1_000_000, totals.garbage);
+    }
+
+    fn printed(stream: &str, garbage: Garbage, pretty: bool) -> String {
+        let mut output = Vec::new();
+        print(stream.as_bytes(), &mut output, Style { garbage, pretty }).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn print_stream() {
+        let stream = "{{<a!>b>}, {<!!>,{}},<x>}\n";
+        assert_eq!("{{<a!>b>},{<!!>,{}},<x>}\n", printed(stream, Garbage::Keep, false));
+        assert_eq!("{{<ab>},{<>,{}},<x>}\n", printed(stream, Garbage::Resolve, false));
+        assert_eq!("{{},{{}}}\n", printed(stream, Garbage::Strip, false));
+        assert_eq!(
+            "{\n  {\n    <ab>\n  },\n  {\n    <>,\n    {}\n  },\n  <x>\n}\n",
+            printed(stream, Garbage::Resolve, true)
+        );
+        assert_eq!("{\n  {},\n  {\n    {}\n  }\n}\n", printed(stream, Garbage::Strip, true));
+        assert_eq!("", printed("<a>", Garbage::Strip, true));
+        assert_eq!("{<aé>}\n", printed("{<!éaé>}", Garbage::Resolve, false));
+
+        let style = Style {
+            garbage: Garbage::Keep,
+            pretty: false,
+        };
+        assert!(matches!(
+            print("{<a>".as_bytes(), io::sink(), style),
+            Err(StreamError::UnclosedGroups { offset: 4, depth: 1 })
+        ));
+    }
+}
@@ -1,225 +1,66 @@
-use std::io;
+extern crate day_09;
+extern crate solver;
 
-extern crate pest;
-#[macro_use]
-extern crate pest_derive;
+use std::env;
+use std::io::{stdin, stdout};
+use std::process;
 
-use pest::Parser;
+use day_09::{Garbage, StreamError, Style};
+use solver::Part;
 
-#[derive(Parser)]
-#[grammar = "stream.pest"]
-struct StreamParser;
+const USAGE: &str = "usage: day-09 [--resolve | --strip] [--pretty] < stream";
 
-#[derive(Debug, PartialEq)]
-enum Token {
-    GroupStart,
-    GroupEnd,
-    GarbageStart,
-    GarbageEnd,
-    Cancel,
-    Other,
-}
-
-#[derive(Debug, Default)]
-struct State {
-    cancel: bool,
-    garbage: bool,
-    depth: u32,
-    scores: Vec<u32>,
-    garbage_total: u32,
-}
-
-impl State {
-    fn new() -> State {
-        State {
-            cancel: false,
-            garbage: false,
-            depth: 0,
-            scores: Vec::new(),
-            garbage_total: 0,
+//
+// The stream is read from stdin as it arrives, rather than all at
+// once. With no options, both parts are reported. With any, the stream
+// is written back out instead: with its garbage kept, kept without the
+// characters cancelled in it (--resolve) or removed (--strip), and
+// optionally indented (--pretty).
+//
+fn main() {
+    let mut style = None;
+    for arg in env::args().skip(1) {
+        let mut current = style.unwrap_or(Style {
+            garbage: Garbage::Keep,
+            pretty: false,
+        });
+        match arg.as_str() {
+            "--resolve" => current.garbage = Garbage::Resolve,
+            "--strip" => current.garbage = Garbage::Strip,
+            "--pretty" => current.pretty = true,
+            _ => {
+                eprintln!("day-09: unknown option: {}", arg);
+                eprintln!("{}", USAGE);
+                process::exit(2);
+            }
         }
+        style = Some(current);
     }
 
-    fn process(&mut self, token: Token) {
-        //println!("state = {:?}, token = {:?}", self, token);
-        if self.cancel == true {
-            self.cancel = false;
-            return;
-        }
-
-        if token == Token::Cancel {
-            self.cancel = true;
-            return;
+    let stdin = stdin();
+    let result = match style {
+        Some(style) => {
+            let stdout = stdout();
+            day_09::print(stdin.lock(), stdout.lock(), style)
         }
-
-        if self.garbage == true {
-            if token == Token::GarbageEnd {
-                self.garbage = false;
-            } else {
-                self.garbage_total += 1;
-            }
-            return;
+        None => day_09::scan(stdin.lock()).map(|totals| {
+            // Both answers are known by now, so neither part can fail
+            let _ = solver::report(&[
+                (Part::One, Ok(Some(totals.score.to_string()))),
+                (Part::Two, Ok(Some(totals.garbage.to_string()))),
+            ]);
+        }),
+    };
+
+    match result {
+        Ok(()) => {}
+        Err(StreamError::Io(e)) => {
+            eprintln!("error: {}", e);
+            process::exit(1);
         }
-
-        match token {
-            Token::GroupStart => {
-                self.depth += 1;
-            }
-            Token::GroupEnd => {
-                self.scores.push(self.depth);
-                self.depth -= 1;
-            }
-            Token::GarbageStart => {
-                self.garbage = true;
-            }
-            _ => {}
+        Err(e) => {
+            eprintln!("error: invalid stream at {}", e);
+            process::exit(1);
         }
     }
-
-    fn total_score(&self) -> u32 {
-        self.scores.iter().sum()
-    }
-
-    fn total_garbage(&self) -> u32 {
-        self.garbage_total
-    }
-}
-
-fn parse_line(stream: &str, state: &mut State) {
-    let pairs = StreamParser::parse_str(Rule::stream, stream).unwrap_or_else(|e| panic!("{}", e));
-
-    //println!("pairs = {:?}", pairs);
-    for pair in pairs {
-        state.process(match pair.as_rule() {
-            Rule::groupstart => Token::GroupStart,
-            Rule::groupend => Token::GroupEnd,
-            Rule::garbagestart => Token::GarbageStart,
-            Rule::garbageend => Token::GarbageEnd,
-            Rule::cancel => Token::Cancel,
-            _ => Token::Other,
-        });
-    }
-}
-
-fn main() {
-    let mut input = String::new();
-
-    io::stdin().read_line(&mut input).unwrap();
-    //println!("input = {:?}", input.trim());
-
-    let mut state = State::new();
-    parse_line(input.trim(), &mut state);
-    //println!("state = {:?}", state);
-    println!("total score = {}", state.total_score());
-    println!("total garbage = {}", state.total_garbage());
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn part1_example_1() {
-        let mut state = State::new();
-        parse_line("{}", &mut state);
-        assert_eq!(1, state.total_score());
-    }
-
-    #[test]
-    fn part1_example_2() {
-        let mut state = State::new();
-        parse_line("{{{}}}", &mut state);
-        assert_eq!(6, state.total_score());
-    }
-
-    #[test]
-    fn part1_example_3() {
-        let mut state = State::new();
-        parse_line("{{},{}}", &mut state);
-        assert_eq!(5, state.total_score());
-    }
-
-    #[test]
-    fn part1_example_4() {
-        let mut state = State::new();
-        parse_line("{{{},{},{{}}}}", &mut state);
-        assert_eq!(16, state.total_score());
-    }
-
-    #[test]
-    fn part1_example_5() {
-        let mut state = State::new();
-        parse_line("{<a>,<a>,<a>,<a>}", &mut state);
-        assert_eq!(1, state.total_score());
-    }
-
-    #[test]
-    fn part1_example_6() {
-        let mut state = State::new();
-        parse_line("{{<ab>},{<ab>},{<ab>},{<ab>}}", &mut state);
-        assert_eq!(9, state.total_score());
-    }
-
-    #[test]
-    fn part1_example_7() {
-        let mut state = State::new();
-        parse_line("{{<!!>},{<!!>},{<!!>},{<!!>}}", &mut state);
-        assert_eq!(9, state.total_score());
-    }
-
-    #[test]
-    fn part1_example_8() {
-        let mut state = State::new();
-        parse_line("{{<a!>},{<a!>},{<a!>},{<ab>}}", &mut state);
-        assert_eq!(3, state.total_score());
-    }
-
-    #[test]
-    fn part2_example_1() {
-        let mut state = State::new();
-        parse_line("<>", &mut state);
-        assert_eq!(0, state.total_garbage());
-    }
-
-    #[test]
-    fn part2_example_2() {
-        let mut state = State::new();
-        parse_line("<random characters>", &mut state);
-        assert_eq!(17, state.total_garbage());
-    }
-
-    #[test]
-    fn part2_example_3() {
-        let mut state = State::new();
-        parse_line("<<<<>", &mut state);
-        assert_eq!(3, state.total_garbage());
-    }
-
-    #[test]
-    fn part2_example_4() {
-        let mut state = State::new();
-        parse_line("<{!>}>", &mut state);
-        assert_eq!(2, state.total_garbage());
-    }
-
-    #[test]
-    fn part2_example_5() {
-        let mut state = State::new();
-        parse_line("<!!>", &mut state);
-        assert_eq!(0, state.total_garbage());
-    }
-
-    #[test]
-    fn part2_example_6() {
-        let mut state = State::new();
-        parse_line("<!!!>>", &mut state);
-        assert_eq!(0, state.total_garbage());
-    }
-
-    #[test]
-    fn part2_example_7() {
-        let mut state = State::new();
-        parse_line("<{o\"i!a,<}i<a>", &mut state);
-        assert_eq!(10, state.total_garbage());
-    }
 }
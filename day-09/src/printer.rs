@@ -0,0 +1,120 @@
+//
+// Writes a stream back out in a normal form. Groups and garbage are
+// separated by single commas and anything else outside garbage is
+// dropped, including characters cancelled there. Pretty printing puts
+// each group's contents on lines of their own, indented by depth, but
+// leaves empty groups as {}.
+//
+use std::io::{self, Write};
+
+// What becomes of garbage
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Garbage {
+    Keep,
+    // Keep it without the ! or the characters they cancel
+    Resolve,
+    Strip,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Style {
+    pub garbage: Garbage,
+    pub pretty: bool,
+}
+
+#[derive(Debug)]
+pub struct Printer {
+    style: Style,
+    depth: usize,
+    // Nothing has been written within the innermost group yet
+    first: bool,
+    // Written but not yet taken
+    buffer: Vec<u8>,
+    written: bool,
+}
+
+impl Printer {
+    pub fn new(style: Style) -> Printer {
+        Printer {
+            style,
+            depth: 0,
+            first: true,
+            buffer: Vec::new(),
+            written: false,
+        }
+    }
+
+    fn new_line(&mut self) {
+        self.buffer.push(b'\n');
+        for _ in 0..self.depth {
+            self.buffer.extend_from_slice(b"  ");
+        }
+    }
+
+    // Separate the next group or garbage from what came before
+    fn item(&mut self) {
+        if !self.first {
+            self.buffer.push(b',');
+        }
+        if self.style.pretty && (!self.first || self.depth > 0) {
+            self.new_line();
+        }
+        self.first = false;
+        self.written = true;
+    }
+
+    pub fn group_start(&mut self) {
+        self.item();
+        self.buffer.push(b'{');
+        self.depth += 1;
+        self.first = true;
+    }
+
+    pub fn group_end(&mut self) {
+        self.depth -= 1;
+        if self.style.pretty && !self.first {
+            self.new_line();
+        }
+        self.buffer.push(b'}');
+        self.first = false;
+    }
+
+    pub fn garbage_start(&mut self) {
+        if self.style.garbage != Garbage::Strip {
+            self.item();
+            self.buffer.push(b'<');
+        }
+    }
+
+    pub fn garbage_character(&mut self, character: &[u8]) {
+        if self.style.garbage != Garbage::Strip {
+            self.buffer.extend_from_slice(character);
+        }
+    }
+
+    // A ! within garbage, or the character it cancels
+    pub fn garbage_cancel(&mut self, character: &[u8]) {
+        if self.style.garbage == Garbage::Keep {
+            self.buffer.extend_from_slice(character);
+        }
+    }
+
+    pub fn garbage_end(&mut self) {
+        if self.style.garbage != Garbage::Strip {
+            self.buffer.push(b'>');
+        }
+    }
+
+    pub fn finish(&mut self) {
+        if self.written {
+            self.buffer.push(b'\n');
+        }
+    }
+
+    // Pass on what has been written so far
+    pub fn flush<W: Write>(&mut self, output: &mut W) -> io::Result<()> {
+        output.write_all(&self.buffer)?;
+        self.buffer.clear();
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+//
+// The structure of a stream, for when more is wanted than its score.
+// The whole stream is taken as a group of depth 0 with no braces of its
+// own, starting at 0 and ending at the length of the stream. Offsets
+// are in bytes from the start of the stream.
+//
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GarbageSpan {
+    // The offsets of the opening < and the closing >
+    pub start: usize,
+    pub end: usize,
+    // Characters of garbage, not counting the ! or what they cancel
+    pub characters: u32,
+    pub cancelled: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Group {
+    // The offsets of the opening { and the closing }
+    pub start: usize,
+    pub end: usize,
+    pub depth: u32,
+    // Characters cancelled within the group but outside any garbage
+    pub cancelled: u32,
+    pub children: Vec<Group>,
+    pub garbage: Vec<GarbageSpan>,
+}
+
+impl Group {
+    // Every group within this one, in the order they open
+    pub fn groups(&self) -> Vec<&Group> {
+        let mut groups = Vec::new();
+        let mut stack: Vec<&Group> = self.children.iter().rev().collect();
+
+        while let Some(group) = stack.pop() {
+            groups.push(group);
+            stack.extend(group.children.iter().rev());
+        }
+
+        groups
+    }
+
+    // The first of the most deeply nested groups
+    pub fn deepest(&self) -> &Group {
+        self.groups()
+            .into_iter()
+            .fold(self, |deepest, group| {
+                if group.depth > deepest.depth {
+                    group
+                } else {
+                    deepest
+                }
+            })
+    }
+
+    // The first of the pieces of garbage with the most characters
+    pub fn largest_garbage(&self) -> Option<&GarbageSpan> {
+        let mut groups = self.groups();
+        groups.insert(0, self);
+        groups
+            .into_iter()
+            .flat_map(|group| group.garbage.iter())
+            .fold(None, |largest: Option<&GarbageSpan>, garbage| match largest {
+                Some(largest) if largest.characters >= garbage.characters => Some(largest),
+                _ => Some(garbage),
+            })
+    }
+
+    // How many groups open at each depth
+    pub fn groups_per_depth(&self) -> BTreeMap<u32, usize> {
+        let mut counts = BTreeMap::new();
+        for group in self.groups() {
+            *counts.entry(group.depth).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+// Builds the groups as the state machine passes through the stream
+#[derive(Debug)]
+pub struct TreeBuilder {
+    open: Vec<Group>,
+    garbage: Option<GarbageSpan>,
+}
+
+impl TreeBuilder {
+    pub fn new() -> TreeBuilder {
+        TreeBuilder {
+            open: vec![Group::default()],
+            garbage: None,
+        }
+    }
+
+    fn innermost(&mut self) -> &mut Group {
+        self.open.last_mut().unwrap()
+    }
+
+    pub fn group_start(&mut self, offset: usize) {
+        let depth = self.open.len() as u32;
+        self.open.push(Group {
+            start: offset,
+            depth,
+            ..Group::default()
+        });
+    }
+
+    pub fn group_end(&mut self, offset: usize) {
+        let mut group = self.open.pop().unwrap();
+        group.end = offset;
+        self.innermost().children.push(group);
+    }
+
+    pub fn garbage_start(&mut self, offset: usize) {
+        self.garbage = Some(GarbageSpan {
+            start: offset,
+            end: offset,
+            characters: 0,
+            cancelled: 0,
+        });
+    }
+
+    pub fn garbage_character(&mut self) {
+        if let Some(ref mut garbage) = self.garbage {
+            garbage.characters += 1;
+        }
+    }
+
+    pub fn garbage_end(&mut self, offset: usize) {
+        if let Some(mut garbage) = self.garbage.take() {
+            garbage.end = offset;
+            self.innermost().garbage.push(garbage);
+        }
+    }
+
+    pub fn cancelled(&mut self) {
+        match self.garbage {
+            Some(ref mut garbage) => garbage.cancelled += 1,
+            None => self.innermost().cancelled += 1,
+        }
+    }
+
+    pub fn finish(mut self, length: usize) -> Group {
+        let mut root = self.open.swap_remove(0);
+        root.end = length;
+        root
+    }
+}
@@ -0,0 +1,97 @@
+extern crate knothash;
+extern crate solver;
+
+use knothash::KnotHash;
+use solver::{Answer, ParseError, Solver};
+
+fn parse_lengths(input: &str) -> Result<Vec<usize>, ParseError> {
+    input
+        .split(',')
+        .map(|x| match x.parse::<usize>() {
+            Ok(length) if length <= knothash::SIZE => Ok(length),
+            Ok(_) => Err(ParseError::token(1, input, x, "length exceeds list size")),
+            Err(e) => Err(ParseError::token(1, input, x, e)),
+        })
+        .collect()
+}
+
+fn do_part1(lengths: &[usize]) -> u32 {
+    //println!("lengths = {:?}", lengths);
+    let mut kh = KnotHash::new(knothash::SIZE);
+    for &length in lengths {
+        kh.twist(length);
+    }
+    //println!("kh = {:?}", kh);
+    kh.product_first_two()
+}
+
+fn do_part2(input: &str) -> String {
+    knothash::hash_hex(input.as_bytes())
+}
+
+pub struct Puzzle {
+    input: String,
+    lengths: Vec<usize>,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let input = input.trim();
+
+        Ok(Puzzle {
+            input: input.to_string(),
+            lengths: parse_lengths(input)?,
+        })
+    }
+
+    fn part1(&self) -> Answer {
+        Ok(Some(do_part1(&self.lengths).to_string()))
+    }
+
+    fn part2(&self) -> Answer {
+        Ok(Some(do_part2(&self.input)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let mut kh = KnotHash::new(5);
+        for length in [3, 4, 1, 5].iter() {
+            kh.twist(*length as usize);
+        }
+        println!("kh = {:?}", kh);
+        assert_eq!(12, kh.product_first_two());
+    }
+
+    #[test]
+    fn try_part2_1() {
+        let x = "".to_string();
+        let z = do_part2(&x);
+        assert_eq!(z, "a2582a3a0e66e6e86e3812dcb672a272");
+    }
+
+    #[test]
+    fn try_part2_2() {
+        let x = "AoC 2017".to_string();
+        let z = do_part2(&x);
+        assert_eq!(z, "33efeb34ea91902bb2f59c9920caa6cd");
+    }
+
+    #[test]
+    fn try_part2_3() {
+        let x = "1,2,3".to_string();
+        let z = do_part2(&x);
+        assert_eq!(z, "3efbe78a8d82f29979031a4aa0b16a9d");
+    }
+
+    #[test]
+    fn try_part2_4() {
+        let x = "1,2,4".to_string();
+        let z = do_part2(&x);
+        assert_eq!(z, "63960835bcdc130f0b66d7ff4f6a5a8e");
+    }
+}
@@ -0,0 +1,133 @@
+extern crate solver;
+
+use solver::{Answer, ParseError, Solver};
+
+#[derive(Debug)]
+enum Direction {
+    N,
+    NE,
+    SE,
+    S,
+    SW,
+    NW,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    x: i32,
+    y: i32,
+    z: i32,
+    maximum_distance: i32,
+}
+
+impl State {
+    fn new() -> State {
+        State::default()
+    }
+
+    fn go(&mut self, direction: Direction) {
+        match direction {
+            Direction::N => {
+                self.x += 1;
+                self.z -= 1;
+            }
+            Direction::NE => {
+                self.x += 1;
+                self.y -= 1;
+            }
+            Direction::SE => {
+                self.z += 1;
+                self.y -= 1;
+            }
+            Direction::S => {
+                self.z += 1;
+                self.x -= 1;
+            }
+            Direction::SW => {
+                self.y += 1;
+                self.x -= 1;
+            }
+            Direction::NW => {
+                self.y += 1;
+                self.z -= 1;
+            }
+        }
+        self.update_maximum_distance();
+    }
+
+    fn update_maximum_distance(&mut self) {
+        for distance in [self.x.abs(), self.y.abs(), self.z.abs()].iter() {
+            if *distance > self.maximum_distance {
+                self.maximum_distance = *distance;
+            }
+        }
+    }
+
+    fn fewest_steps(&self) -> i32 {
+        *[self.x.abs(), self.y.abs(), self.z.abs()]
+            .iter()
+            .max()
+            .unwrap()
+    }
+}
+
+fn parse_line(input: &str) -> Result<Vec<Direction>, ParseError> {
+    let mut steps = Vec::new();
+
+    let directions: Vec<&str> = input.split(',').collect();
+    for direction in directions {
+        match direction {
+            "n" => {
+                steps.push(Direction::N);
+            }
+            "ne" => {
+                steps.push(Direction::NE);
+            }
+            "se" => {
+                steps.push(Direction::SE);
+            }
+            "s" => {
+                steps.push(Direction::S);
+            }
+            "sw" => {
+                steps.push(Direction::SW);
+            }
+            "nw" => {
+                steps.push(Direction::NW);
+            }
+            _ => {
+                return Err(ParseError::token(1, input, direction, "unknown direction"));
+            }
+        }
+    }
+
+    Ok(steps)
+}
+
+pub struct Puzzle {
+    state: State,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let directions = parse_line(input.trim())?;
+        //println!("directions = {:?}", directions);
+
+        let mut state = State::new();
+
+        for direction in directions {
+            state.go(direction);
+        }
+        //println!("state = {:?}", state);
+
+        Ok(Puzzle { state })
+    }
+
+    fn part1(&self) -> Answer {
+        Ok(Some(self.state.fewest_steps().to_string()))
+    }
+
+    fn part2(&self) -> Answer {
+        Ok(Some(self.state.maximum_distance.to_string()))
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::Entry;
+
+extern crate pest;
+#[macro_use]
+extern crate pest_derive;
+extern crate solver;
+
+use pest::Parser;
+use solver::{Answer, ParseError, Solver};
+
+#[derive(Parser)]
+#[grammar = "linking.pest"]
+struct LinkingParser;
+
+#[derive(Debug, Default)]
+struct Linking {
+    id: u32,
+    piped: Vec<u32>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct State {
+    pipes: HashMap<u32, Vec<u32>>,
+}
+
+impl State {
+    fn new() -> State {
+        State::default()
+    }
+
+    fn add_pipes(&mut self, id: u32, piped: Vec<u32>) {
+        match self.pipes.entry(id) {
+            Entry::Vacant(vacant) => {
+                vacant.insert(piped);
+            }
+            Entry::Occupied(_) => {
+                println!("occupied unexpectedly");
+            }
+        }
+    }
+
+    fn count_connected(&self, id: u32) -> u32 {
+        let mut connected = HashSet::new();
+        let mut stack: Vec<u32> = Vec::new();
+
+        stack.push(id);
+
+        loop {
+            match stack.pop() {
+                None => {
+                    break;
+                }
+                Some(id) => {
+                    if let Some(piped) = self.pipes.get(&id) {
+                        for pid in piped {
+                            if !connected.contains(pid) {
+                                stack.push(*pid);
+                            }
+                        }
+                    }
+                    connected.insert(id);
+                }
+            }
+        }
+
+        connected.len() as u32
+    }
+
+    fn purge_group(&mut self, id: u32) {
+        let mut stack: Vec<u32> = Vec::new();
+
+        stack.push(id);
+
+        loop {
+            match stack.pop() {
+                None => {
+                    break;
+                }
+                Some(id) => {
+                    if let Some(piped) = self.pipes.get(&id) {
+                        stack.extend(piped);
+                    }
+                    self.pipes.remove(&id);
+                }
+            }
+        }
+    }
+
+    fn count_groups(&mut self) -> u32 {
+        let mut group_count: u32 = 0;
+
+        loop {
+            let id: u32;
+            if let Some(key) = self.pipes.keys().next() {
+                id = *key;
+            } else {
+                break;
+            }
+            self.purge_group(id);
+            group_count += 1;
+        }
+
+        group_count
+    }
+}
+
+fn parse_line(line: &str) -> Result<Linking, ParseError> {
+    let pairs = LinkingParser::parse(Rule::linking, line).map_err(ParseError::from_pest)?;
+
+    let mut linking = Linking::default();
+
+    for pair in pairs {
+        let rule = pair.as_rule();
+        let span = pair.clone().into_span();
+        let number = span.as_str()
+            .parse()
+            .map_err(|e| ParseError::token(1, line, span.as_str(), e));
+        match rule {
+            Rule::id => {
+                linking.id = number?;
+            }
+            Rule::piped => {
+                linking.piped.push(number?);
+            }
+            _ => {
+                println!("unknown rule {:?}", rule);
+            }
+        }
+    }
+
+    Ok(linking)
+}
+
+pub struct Puzzle {
+    state: State,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let linkings: Vec<Linking> = solver::parse_lines(input, parse_line)?;
+        //println!("linkings = {:?}", linkings);
+
+        let mut state = State::new();
+        for linking in linkings {
+            state.add_pipes(linking.id, linking.piped);
+        }
+        //println!("state = {:?}", state);
+
+        Ok(Puzzle { state })
+    }
+
+    fn part1(&self) -> Answer {
+        Ok(Some(self.state.count_connected(0).to_string()))
+    }
+
+    fn part2(&self) -> Answer {
+        // Counting groups purges them from the state
+        let mut state = self.state.clone();
+        Ok(Some(state.count_groups().to_string()))
+    }
+}
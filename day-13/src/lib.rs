@@ -0,0 +1,153 @@
+extern crate solver;
+
+use std::collections::HashMap;
+use solver::{Answer, ParseError, Solver};
+
+#[derive(Debug, PartialEq, Clone)]
+enum Direction {
+    Up,
+    Down,
+}
+
+#[derive(Debug, Clone)]
+struct Layer {
+    depth: u32,
+    range: u32,
+    scanner: u32,
+    direction: Direction,
+}
+
+impl Layer {
+    fn new(depth: u32, range: u32) -> Layer {
+        Layer {
+            depth,
+            range,
+            scanner: 0,
+            direction: Direction::Down,
+        }
+    }
+
+    fn tick(&mut self) {
+        match self.direction {
+            Direction::Down => {
+                self.scanner += 1;
+                if self.scanner == self.range - 1 {
+                    self.direction = Direction::Up;
+                }
+            }
+            Direction::Up => {
+                self.scanner -= 1;
+                if self.scanner == 0 {
+                    self.direction = Direction::Down;
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    layer_hm: HashMap<u32, Layer>,
+    severity: u32,
+}
+
+impl State {
+    fn new(layers: Vec<Layer>) -> State {
+        let mut layer_hm = HashMap::new();
+
+        for layer in layers {
+            layer_hm.insert(layer.depth, layer);
+        }
+
+        State {
+            layer_hm,
+            severity: 0,
+        }
+    }
+
+    fn maximum_depth(&self) -> u32 {
+        let mut maximum_depth: u32 = 0;
+        for depth in self.layer_hm.keys() {
+            if *depth > maximum_depth {
+                maximum_depth = *depth;
+            }
+        }
+        maximum_depth
+    }
+
+    fn travel(&mut self) {
+        for depth in 0..self.maximum_depth() + 1 {
+            let mut severity: u32 = 0;
+            if let Some(layer) = self.layer_hm.get(&depth) {
+                if layer.scanner == 0 {
+                    severity = layer.depth * layer.range;
+                }
+            }
+            self.severity += severity;
+            for layer in self.layer_hm.values_mut() {
+                layer.tick();
+            }
+        }
+    }
+}
+
+//
+// This is far more efficient than a brute force approach
+//
+fn find_minimal_safe_delay(layers: &Vec<Layer>) -> u32 {
+    let mut delay: u32 = 0;
+    loop {
+        let mut caught: bool = false;
+        for layer in layers {
+            if (delay + layer.depth).is_multiple_of((layer.range - 1) * 2) {
+                caught = true;
+                break;
+            }
+        }
+        if caught {
+            delay += 1;
+        } else {
+            break;
+        }
+    }
+    delay
+}
+
+fn parse_line(line: &str) -> Result<Layer, ParseError> {
+    let mut values = line.splitn(2, ": ");
+    let mut next_value = || -> Result<u32, ParseError> {
+        match values.next() {
+            Some(value) => value
+                .parse()
+                .map_err(|e| ParseError::token(1, line, value, e)),
+            None => Err(ParseError::new(1, line.len() + 1, line, "expected \": <range>\"")),
+        }
+    };
+    let depth = next_value()?;
+    let range = next_value()?;
+
+    Ok(Layer::new(depth, range))
+}
+
+pub struct Puzzle {
+    layers: Vec<Layer>,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let layers: Vec<Layer> = solver::parse_lines(input, parse_line)?;
+        //println!("layers = {:?}", layers);
+
+        Ok(Puzzle { layers })
+    }
+
+    fn part1(&self) -> Answer {
+        let mut state = State::new(self.layers.clone());
+        state.travel();
+        Ok(Some(state.severity.to_string()))
+    }
+
+    fn part2(&self) -> Answer {
+        Ok(Some(find_minimal_safe_delay(&self.layers).to_string()))
+    }
+}
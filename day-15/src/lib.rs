@@ -0,0 +1,88 @@
+extern crate solver;
+
+use solver::{Answer, ParseError, Solver};
+
+const DIVISOR: u64 = 2147483647;
+const GEN_A_FACTOR: u64 = 16807;
+const GEN_B_FACTOR: u64 = 48271;
+
+#[derive(Debug)]
+struct Generator {
+    value: u64,
+    factor: u64,
+    criteria: u64,
+}
+
+impl Generator {
+    fn new(start_value: u64, factor: u64, criteria: u64) -> Generator {
+        Generator {
+            value: start_value,
+            factor,
+            criteria,
+        }
+    }
+
+    fn create_next_value(&mut self) {
+        loop {
+            self.value = (self.value * self.factor) % DIVISOR;
+            if self.value.is_multiple_of(self.criteria) {
+                break;
+            }
+        }
+    }
+}
+
+fn execute(gen_a: &mut Generator, gen_b: &mut Generator, judge_limit: u64) -> u32 {
+    let mut matches: u32 = 0;
+
+    for _ in 0..judge_limit {
+        gen_a.create_next_value();
+        gen_b.create_next_value();
+        if gen_a.value & 0xffff == gen_b.value & 0xffff {
+            matches += 1;
+        }
+    }
+
+    matches
+}
+
+// Each generator is described by a line such as
+// "Generator A starts with 65"; the starting value comes last.
+fn parse_start(line: &str) -> Result<u64, ParseError> {
+    let value = line.split(' ').next_back().unwrap_or(line);
+    value.parse().map_err(|e| ParseError::token(1, line, value, e))
+}
+
+pub struct Puzzle {
+    start_a: u64,
+    start_b: u64,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let starts: Vec<u64> = solver::parse_lines(input, parse_start)?;
+        if starts.len() != 2 {
+            let line = input.lines().count() + 1;
+            return Err(ParseError::new(line, 1, "", "expected exactly two generators"));
+        }
+
+        Ok(Puzzle {
+            start_a: starts[0],
+            start_b: starts[1],
+        })
+    }
+
+    fn part1(&self) -> Answer {
+        let mut gen_a = Generator::new(self.start_a, GEN_A_FACTOR, 1);
+        let mut gen_b = Generator::new(self.start_b, GEN_B_FACTOR, 1);
+        let matches: u32 = execute(&mut gen_a, &mut gen_b, 40000000);
+        Ok(Some(matches.to_string()))
+    }
+
+    fn part2(&self) -> Answer {
+        let mut gen_a = Generator::new(self.start_a, GEN_A_FACTOR, 4);
+        let mut gen_b = Generator::new(self.start_b, GEN_B_FACTOR, 8);
+        let matches: u32 = execute(&mut gen_a, &mut gen_b, 5000000);
+        Ok(Some(matches.to_string()))
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::iter::FromIterator;
+
+extern crate pest;
+#[macro_use]
+extern crate pest_derive;
+extern crate solver;
+
+use pest::Parser;
+use solver::{Answer, ParseError, Solver};
+
+#[derive(Parser)]
+#[grammar = "dance.pest"]
+struct DanceParser;
+
+#[derive(Debug)]
+enum Step {
+    Spin(usize),
+    Exchange(usize, usize),
+    Partner(char, char),
+}
+
+#[derive(Debug, Default)]
+struct State {
+    line: Vec<char>,
+}
+
+impl State {
+    fn new() -> State {
+        let line = "abcdefghijklmnop".chars().collect();
+        State { line }
+    }
+
+    fn dance(&mut self, step: &Step) {
+        match *step {
+            Step::Spin(size) => {
+                let mut tail: Vec<char> = self.line.split_off(16 - size);
+                tail.extend(self.line.iter());
+                self.line = tail;
+            }
+            Step::Exchange(position1, position2) => {
+                self.line.swap(position1, position2);
+            }
+            Step::Partner(program1, program2) => {
+                let position1 = self.line.iter().position(|&x| x == program1).unwrap();
+                let position2 = self.line.iter().position(|&x| x == program2).unwrap();
+                self.line.swap(position1, position2);
+            }
+        }
+    }
+}
+
+fn parse_line(line: &str) -> Result<Vec<Step>, ParseError> {
+    let pairs = DanceParser::parse(Rule::dance, line).map_err(ParseError::from_pest)?;
+
+    let mut steps: Vec<Step> = Vec::new();
+
+    let mut position1: usize = 0;
+    let mut program1: char = '*';
+
+    for pair in pairs {
+        let rule = pair.as_rule();
+        let span = pair.clone().into_span();
+        let text = span.as_str().to_string();
+        // Spins may move the whole line of sixteen programs, but
+        // exchanges must name positions within it
+        let number = |limit: usize| -> Result<usize, ParseError> {
+            match text.parse::<usize>() {
+                Ok(n) if n <= limit => Ok(n),
+                Ok(_) => Err(ParseError::token(1, line, span.as_str(), "out of range")),
+                Err(e) => Err(ParseError::token(1, line, span.as_str(), e)),
+            }
+        };
+        match rule {
+            Rule::size => {
+                let size: usize = number(16)?;
+                steps.push(Step::Spin(size));
+            }
+            Rule::position1 => {
+                position1 = number(15)?;
+            }
+            Rule::position2 => {
+                let position2: usize = number(15)?;
+                steps.push(Step::Exchange(position1, position2));
+            }
+            Rule::program1 => {
+                program1 = text.chars().next().unwrap();
+            }
+            Rule::program2 => {
+                let program2 = text.chars().next().unwrap();
+                steps.push(Step::Partner(program1, program2));
+            }
+            _ => {
+                unimplemented!("parse_line");
+            }
+        }
+    }
+
+    Ok(steps)
+}
+
+fn dance_once(steps: &[Step]) -> String {
+    let mut state = State::new();
+    for step in steps.iter() {
+        state.dance(step);
+    }
+    String::from_iter(&state.line)
+}
+
+fn dance_a_billion_times(steps: &[Step]) -> String {
+    // I found an internet discussion of Landau's function, which
+    // establishes that a cycle is inevitable.
+
+    // Use this to identify the cycle length
+    let mut transforms: HashMap<Vec<char>, Vec<char>> = HashMap::new();
+    // Track all permutations in order; index into this once
+    // the offset is known
+    let mut permutations: Vec<Vec<char>> = Vec::new();
+
+    let mut state = State::new();
+    loop {
+        let line_before = state.line.clone();
+        for step in steps.iter() {
+            state.dance(step);
+        }
+        let line_after = state.line.clone();
+        let transforms_length: usize = transforms.len();
+        match transforms.entry(line_before) {
+            Entry::Vacant(v) => {
+                permutations.push(line_after.clone());
+                v.insert(line_after);
+            }
+            Entry::Occupied(_) => {
+                let offset = 1000000000 % transforms_length;
+                //println!("1000000000 % {} = {}", transforms_length, offset);
+                return String::from_iter(&permutations[offset - 1]);
+            }
+        }
+    }
+}
+
+pub struct Puzzle {
+    steps: Vec<Step>,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let steps: Vec<Step> = parse_line(input.trim())?;
+        //    println!("steps = {:?}", steps);
+
+        Ok(Puzzle { steps })
+    }
+
+    fn part1(&self) -> Answer {
+        Ok(Some(dance_once(&self.steps)))
+    }
+
+    fn part2(&self) -> Answer {
+        Ok(Some(dance_a_billion_times(&self.steps)))
+    }
+}
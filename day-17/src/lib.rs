@@ -0,0 +1,97 @@
+extern crate solver;
+
+use solver::{Answer, ParseError, Solver};
+
+#[derive(Debug)]
+struct Spinlock {
+    buffer: Vec<u64>,
+    position: usize,
+    steps: usize,
+}
+
+impl Spinlock {
+    fn new(steps: usize) -> Spinlock {
+        let buffer = vec![0];
+
+        let position = 0;
+
+        Spinlock {
+            buffer,
+            position,
+            steps,
+        }
+    }
+
+    fn insert(&mut self, value: u64) {
+        self.position = 1 + (self.position + self.steps) % self.buffer.len();
+        self.buffer.insert(self.position, value);
+    }
+
+    //
+    // Customized function for part 2: since we only care about
+    // values at position 1, we can push other values onto the end.
+    // For Vec, push() is much faster than insert().
+    //
+    fn insert_or_push(&mut self, value: u64) {
+        self.position = 1 + (self.position + self.steps) % self.buffer.len();
+        if self.position == 1 {
+            self.buffer.insert(self.position, value);
+        } else {
+            self.buffer.push(value);
+        }
+    }
+}
+
+fn do_part1(steps: usize) -> u64 {
+    let mut spinlock = Spinlock::new(steps);
+
+    for value in 1..2018 {
+        spinlock.insert(value);
+    }
+
+    for index in 0..spinlock.buffer.len() {
+        if spinlock.buffer[index] == 2017 {
+            return spinlock.buffer[index + 1];
+        }
+    }
+
+    unreachable!("2017 was inserted");
+}
+
+fn do_part2(steps: usize) -> u64 {
+    let mut spinlock = Spinlock::new(steps);
+
+    for value in 1..50000000 {
+        spinlock.insert_or_push(value);
+    }
+
+    for index in 0..spinlock.buffer.len() {
+        if spinlock.buffer[index] == 0 {
+            return spinlock.buffer[index + 1];
+        }
+    }
+
+    unreachable!("0 is always in the buffer");
+}
+
+pub struct Puzzle {
+    steps: usize,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let line = input.trim();
+        let steps = line.parse()
+            .map_err(|e| ParseError::token(1, line, line, e))?;
+
+        Ok(Puzzle { steps })
+    }
+
+    fn part1(&self) -> Answer {
+        Ok(Some(do_part1(self.steps).to_string()))
+    }
+
+    fn part2(&self) -> Answer {
+        Ok(Some(do_part2(self.steps).to_string()))
+    }
+}
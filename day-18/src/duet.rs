@@ -1,264 +1,140 @@
-use instruction::Instruction;
+use vm::{Arithmetic, Debuggee, Instruction, Io, Machine, Messages, Sound, Status, Tracer, VmError};
 
-use std::collections::HashMap;
+// Why a duet program returned from execute
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ExitReason {
+    // The instruction pointer ran off the end of the program
+    Halted,
+    // At a rcv with nothing to receive
+    Blocked,
+    // A rcv recovered the last frequency played
+    Recovered(i64),
+    // The step limit was reached
+    StepLimit,
+}
 
-use std::sync::mpsc::{Receiver, Sender};
+// What a program stopped at rcv is waiting for, which depends on
+// whether it is playing sounds or passing messages
+pub trait Rcv: Io {
+    fn stopped(&self) -> ExitReason;
+}
 
-#[derive(Debug)]
-pub struct Duet {
-    code_segment: Vec<Instruction>,
-    instruction_pointer: i64,
-    registers: HashMap<char, i64>,
-    frequency: i64,
-    program_id: i64,
-    tx: Sender<i64>,
-    rx: Receiver<i64>,
-    send_counter: u32,
-    part2_flag: bool,
+impl Rcv for Sound {
+    fn stopped(&self) -> ExitReason {
+        ExitReason::Recovered(self.recovered().unwrap())
+    }
 }
 
-impl Duet {
-    pub fn new(
-        instructions: Vec<Instruction>,
-        program_id: i64,
-        tx: Sender<i64>,
-        rx: Receiver<i64>,
-        part2_flag: bool,
-    ) -> Duet {
-        let mut registers: HashMap<char, i64> = HashMap::new();
-        for instruction in instructions.iter() {
-            match instruction {
-                &Instruction::NoOp => {}
-                &Instruction::SndR(register) => {
-                    registers.entry(register).or_insert(0);
-                }
-                &Instruction::SndN(_) => {}
-                &Instruction::SetR(register1, register2) => {
-                    registers.entry(register1).or_insert(0);
-                    registers.entry(register2).or_insert(0);
-                }
-                &Instruction::SetN(register, _) => {
-                    registers.entry(register).or_insert(0);
-                }
-                &Instruction::AddR(register1, register2) => {
-                    registers.entry(register1).or_insert(0);
-                    registers.entry(register2).or_insert(0);
-                }
-                &Instruction::AddN(register, _) => {
-                    registers.entry(register).or_insert(0);
-                }
-                &Instruction::MulR(register1, register2) => {
-                    registers.entry(register1).or_insert(0);
-                    registers.entry(register2).or_insert(0);
-                }
-                &Instruction::MulN(register, _) => {
-                    registers.entry(register).or_insert(0);
-                }
-                &Instruction::ModR(register1, register2) => {
-                    registers.entry(register1).or_insert(0);
-                    registers.entry(register2).or_insert(0);
-                }
-                &Instruction::ModN(register, _) => {
-                    registers.entry(register).or_insert(0);
-                }
-                &Instruction::RcvR(register) => {
-                    registers.entry(register).or_insert(0);
-                }
-                &Instruction::RcvN(_) => {}
-                &Instruction::JgzRR(register1, register2) => {
-                    registers.entry(register1).or_insert(0);
-                    registers.entry(register2).or_insert(0);
-                }
-                &Instruction::JgzRN(register, _) => {
-                    registers.entry(register).or_insert(0);
-                }
-                &Instruction::JgzNR(_, register) => {
-                    registers.entry(register).or_insert(0);
-                }
-                &Instruction::JgzNN(_, _) => {}
-            }
-        }
+impl Rcv for Messages {
+    fn stopped(&self) -> ExitReason {
+        ExitReason::Blocked
+    }
+}
 
-        if part2_flag == true {
-            if let Some(value) = registers.get_mut(&'p') {
-                *value = program_id;
-            }
-        }
+//
+// A duet program is a register machine whose snd and rcv either play
+// sounds (part 1) or pass messages to another program (part 2).
+//
+#[derive(Debug)]
+pub struct Duet<I: Io> {
+    machine: Machine<I>,
+    steps: u64,
+    step_limit: Option<u64>,
+}
 
+impl<I: Io> Duet<I> {
+    pub fn new(instructions: Vec<Instruction>, io: I) -> Duet<I> {
         Duet {
-            code_segment: instructions,
-            instruction_pointer: 0,
-            registers: registers,
-            frequency: 0,
-            program_id: program_id,
-            tx: tx,
-            rx: rx,
-            send_counter: 0,
-            part2_flag: part2_flag,
+            machine: Machine::new(instructions, io),
+            steps: 0,
+            step_limit: None,
         }
     }
 
-    pub fn execute(&mut self) {
-        let mut jumped: bool = false;
+    pub fn set_arithmetic(&mut self, arithmetic: Arithmetic) {
+        self.machine.set_arithmetic(arithmetic);
+    }
+
+    // The most instructions to execute, over every call to execute
+    pub fn set_step_limit(&mut self, step_limit: Option<u64>) {
+        self.step_limit = step_limit;
+    }
+}
+
+impl<I: Rcv> Duet<I> {
+    //
+    // In part 1, return upon the first recovered frequency. In part 2,
+    // return once blocked on rcv with nothing to receive; execution
+    // resumes at the same rcv when called again. Either way, return
+    // if the program halts or uses up its steps.
+    //
+    pub fn execute(&mut self) -> Result<ExitReason, VmError> {
         loop {
-            match self.code_segment[self.instruction_pointer as usize] {
-                Instruction::NoOp => {}
-                Instruction::SndR(register) => {
-                    if self.part2_flag == false {
-                        if let Some(value) = self.registers.get(&register) {
-                            self.frequency = *value;
-                        }
-                    } else {
-                        if let Some(value) = self.registers.get(&register) {
-                            self.tx.send(*value).unwrap();
-                            self.send_counter += 1;
-                            println!(
-                                "[{}] ({}) send = {}",
-                                self.program_id, self.send_counter, *value
-                            );
-                        };
-                    }
-                }
-                Instruction::SndN(number) => {
-                    if self.part2_flag == false {
-                        self.frequency = number;
-                    } else {
-                        self.tx.send(number).unwrap();
-                        self.send_counter += 1;
-                        println!(
-                            "[{}] ({}) send = {}",
-                            self.program_id, self.send_counter, number
-                        );
-                    }
-                }
-                Instruction::SetR(register1, register2) => {
-                    let mut new_value = 0;
-                    if let Some(value) = self.registers.get(&register2) {
-                        new_value = *value;
-                    }
-                    if let Some(value) = self.registers.get_mut(&register1) {
-                        *value = new_value;
-                    }
-                }
-                Instruction::SetN(register, number) => {
-                    if let Some(value) = self.registers.get_mut(&register) {
-                        *value = number;
-                    };
-                }
-                Instruction::AddR(register1, register2) => {
-                    let mut addend = 0;
-                    if let Some(value) = self.registers.get(&register2) {
-                        addend = *value;
-                    }
-                    if let Some(value) = self.registers.get_mut(&register1) {
-                        *value += addend;
-                    }
-                }
-                Instruction::AddN(register, number) => {
-                    if let Some(value) = self.registers.get_mut(&register) {
-                        *value += number;
-                    }
-                }
-                Instruction::MulR(register1, register2) => {
-                    let mut factor = 0;
-                    if let Some(value) = self.registers.get(&register2) {
-                        factor = *value;
-                    }
-                    if let Some(value) = self.registers.get_mut(&register1) {
-                        *value *= factor;
-                    }
-                }
-                Instruction::MulN(register, number) => {
-                    if let Some(value) = self.registers.get_mut(&register) {
-                        *value *= number;
-                    }
-                }
-                Instruction::ModR(register1, register2) => {
-                    let mut divisor = 0;
-                    if let Some(value) = self.registers.get(&register2) {
-                        divisor = *value;
-                    }
-                    if let Some(value) = self.registers.get_mut(&register1) {
-                        *value = *value % divisor;
-                    }
-                }
-                Instruction::ModN(register, number) => {
-                    if let Some(value) = self.registers.get_mut(&register) {
-                        *value = *value % number;
-                    }
-                }
-                Instruction::RcvR(register) => {
-                    if self.part2_flag == false {
-                        if let Some(value) = self.registers.get(&register) {
-                            if *value != 0 {
-                                println!("part 1: frequency = {}", self.frequency);
-                                return;
-                            }
-                        }
-                    } else {
-                        if let Some(value) = self.registers.get_mut(&register) {
-                            *value = self.rx.recv().unwrap();
-                            println!(
-                                "[{}] ({}) recv = {}",
-                                self.program_id, self.send_counter, *value
-                            );
-                        }
-                    }
-                }
-                Instruction::RcvN(number) => {
-                    if self.part2_flag == false {
-                        if number != 0 {
-                            println!("part 1: frequency = {}", self.frequency);
-                            return;
-                        }
-                    } else {
-                        unimplemented!("RcvN");
-                    }
-                }
-                Instruction::JgzRR(register1, register2) => {
-                    let mut x = 0;
-                    if let Some(value) = self.registers.get(&register1) {
-                        x = *value;
-                    }
-                    if let Some(offset) = self.registers.get(&register2) {
-                        if x > 0 {
-                            self.instruction_pointer += *offset;
-                            jumped = true;
-                        }
-                    }
-                }
-                Instruction::JgzRN(register, number) => {
-                    let mut x = 0;
-                    if let Some(value) = self.registers.get(&register) {
-                        x = *value;
-                    }
-                    if x > 0 {
-                        self.instruction_pointer += number;
-                        jumped = true;
-                    }
-                }
-                Instruction::JgzNR(number, register) => {
-                    if let Some(offset) = self.registers.get(&register) {
-                        if number > 0 {
-                            self.instruction_pointer += *offset;
-                            jumped = true;
-                        }
-                    }
-                }
-                Instruction::JgzNN(number1, number2) => {
-                    if number1 > 0 {
-                        self.instruction_pointer += number2;
-                        jumped = true;
-                    }
+            if let Some(step_limit) = self.step_limit {
+                if self.steps >= step_limit {
+                    return Ok(ExitReason::StepLimit);
                 }
             }
-            if jumped == false {
-                self.instruction_pointer += 1;
+            match self.machine.step()? {
+                Status::Running => self.steps += 1,
+                Status::Stopped => return Ok(self.machine.io().stopped()),
+                Status::Halted => return Ok(ExitReason::Halted),
             }
-            jumped = false;
-            // Since the puzzle presumes that rcv will be executed, ignore
-            // the possibility of continuing or jumping off either end
-            // of the progam.
         }
     }
 }
+
+impl<I: Io> Debuggee for Duet<I> {
+    fn step(&mut self) -> Result<Status, VmError> {
+        self.machine.step()
+    }
+
+    fn instruction_pointer(&self) -> i64 {
+        self.machine.instruction_pointer()
+    }
+
+    fn current_instruction(&self) -> Option<Instruction> {
+        self.machine.current_instruction().cloned()
+    }
+
+    fn registers(&self) -> Vec<(char, i64)> {
+        self.machine.registers()
+    }
+
+    fn set_tracer(&mut self, tracer: Option<Box<dyn Tracer>>) {
+        self.machine.set_tracer(tracer);
+    }
+}
+
+impl Duet<Messages> {
+    // Each program starts with its own id in register p
+    pub fn with_program_id(
+        instructions: Vec<Instruction>,
+        program_id: i64,
+        io: Messages,
+    ) -> Duet<Messages> {
+        let mut duet = Duet::new(instructions, io);
+        duet.machine.set_register('p', program_id);
+        duet
+    }
+
+    pub fn send_counter(&self) -> u32 {
+        self.machine.io().send_counter()
+    }
+
+    pub fn receive_counter(&self) -> u32 {
+        self.machine.io().receive_counter()
+    }
+
+    pub fn deliver(&mut self, value: i64) {
+        self.machine.io_mut().deliver(value);
+    }
+
+    pub fn take_sent(&mut self) -> Vec<i64> {
+        self.machine.io_mut().take_sent()
+    }
+
+    pub fn has_pending(&self) -> bool {
+        self.machine.io().has_pending()
+    }
+}
@@ -0,0 +1,304 @@
+extern crate solver;
+extern crate vm;
+
+mod duet;
+mod scheduler;
+mod topology;
+
+use duet::Duet;
+pub use duet::ExitReason;
+use scheduler::Scheduler;
+pub use scheduler::Report;
+pub use topology::{Topology, MAX_PROGRAMS};
+use solver::{Answer, ParseError, Part, SolveError, Solver};
+use vm::{parse_line, Arithmetic, Debugger, Instruction, Messages, Sound, VmError};
+
+use std::io::{self, BufRead, Write};
+
+// How the programs are run: by default, overflow is an error and
+// there is no limit on the number of steps
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Settings {
+    pub arithmetic: Arithmetic,
+    pub step_limit: Option<u64>,
+}
+
+fn configure<I: duet::Rcv>(duet: &mut Duet<I>, settings: Settings) {
+    duet.set_arithmetic(settings.arithmetic);
+    duet.set_step_limit(settings.step_limit);
+}
+
+fn do_part1(instructions: &[Instruction], settings: Settings) -> Result<ExitReason, VmError> {
+    let mut duet = Duet::new(instructions.to_vec(), Sound::new());
+    configure(&mut duet, settings);
+    duet.execute()
+}
+
+fn run_network(
+    instructions: &[Instruction],
+    topology: &Topology,
+    settings: Settings,
+) -> Result<Vec<Report>, VmError> {
+    let mut scheduler = Scheduler::new(instructions, topology);
+    scheduler.set_arithmetic(settings.arithmetic);
+    scheduler.set_step_limit(settings.step_limit);
+    scheduler.run()
+}
+
+// Run both programs until they deadlock, then report how many values
+// program 1 sent; None if either ran out of steps first
+fn do_part2(instructions: &[Instruction], settings: Settings) -> Result<Option<u32>, VmError> {
+    let reports = run_network(instructions, &Topology::ring(2), settings)?;
+    if reports.iter().any(|report| report.exit == ExitReason::StepLimit) {
+        return Ok(None);
+    }
+    Ok(Some(reports[1].sent))
+}
+
+pub struct Puzzle {
+    instructions: Vec<Instruction>,
+    settings: Settings,
+}
+
+impl Puzzle {
+    // Fail, naming every instruction the puzzle does not allow
+    fn validate(&self) -> Result<(), SolveError> {
+        let unsupported: Vec<String> = vm::validate(&self.instructions)
+            .iter()
+            .map(|unsupported| unsupported.to_string())
+            .collect();
+        if unsupported.is_empty() {
+            return Ok(());
+        }
+        Err(SolveError::new(unsupported.join("; ")))
+    }
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        Ok(Puzzle {
+            instructions: solver::parse_lines(input, parse_line)?,
+            settings: Settings::default(),
+        })
+    }
+
+    fn part1(&self) -> Answer {
+        self.validate()?;
+        match do_part1(&self.instructions, self.settings) {
+            Ok(ExitReason::Recovered(frequency)) => Ok(Some(frequency.to_string())),
+            Ok(ExitReason::StepLimit) => Err(SolveError::new("step limit reached")),
+            Ok(_) => Ok(None),
+            Err(e) => Err(SolveError::new(e)),
+        }
+    }
+
+    fn part2(&self) -> Answer {
+        self.validate()?;
+        match do_part2(&self.instructions, self.settings) {
+            Ok(Some(send_counter)) => Ok(Some(send_counter.to_string())),
+            Ok(None) => Err(SolveError::new("step limit reached")),
+            Err(e) => Err(SolveError::new(e)),
+        }
+    }
+}
+
+impl Puzzle {
+    pub fn set_settings(&mut self, settings: Settings) {
+        self.settings = settings;
+    }
+
+    //
+    // Run a copy of the program for each program in the topology, as
+    // in part 2, until the network deadlocks. The program must be one
+    // which part 2 would run.
+    //
+    pub fn network(&self, topology: &Topology) -> Result<Vec<Report>, SolveError> {
+        self.validate()?;
+        run_network(&self.instructions, topology, self.settings).map_err(SolveError::new)
+    }
+
+    // The program as an annotated assembly listing
+    pub fn disassemble(&self) -> String {
+        vm::disassemble(&self.instructions)
+    }
+
+    //
+    // Step through a single program under the debugger, reading
+    // commands from input. In part 2 the other program is absent, so
+    // rcv stops whenever nothing has been sent to it.
+    //
+    pub fn debug<R: BufRead, W: Write>(&self, part: Part, input: R, output: W) -> io::Result<()> {
+        let mut debugger = Debugger::new();
+        match part {
+            Part::One => {
+                let mut duet = Duet::new(self.instructions.clone(), Sound::new());
+                configure(&mut duet, self.settings);
+                debugger.run(&mut duet, input, output)?;
+            }
+            Part::Two => {
+                let mut duet = Duet::with_program_id(self.instructions.clone(), 0, Messages::new());
+                configure(&mut duet, self.settings);
+                debugger.run(&mut duet, input, output)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vm::Debuggee;
+
+    fn parse(input: &str) -> Vec<Instruction> {
+        solver::parse_lines(input, parse_line).unwrap()
+    }
+
+    fn arithmetic(arithmetic: Arithmetic) -> Settings {
+        Settings {
+            arithmetic,
+            ..Settings::default()
+        }
+    }
+
+    fn step_limit(step_limit: u64) -> Settings {
+        Settings {
+            step_limit: Some(step_limit),
+            ..Settings::default()
+        }
+    }
+
+    #[test]
+    fn part1_provided_example() {
+        let instructions = parse(
+            "set a 1\nadd a 2\nmul a a\nmod a 5\nsnd a\nset a 0\nrcv a\njgz a -1\nset a 1\njgz a -2\n",
+        );
+        assert_eq!(Ok(ExitReason::Recovered(4)), do_part1(&instructions, Settings::default()));
+    }
+
+    #[test]
+    fn part2_provided_example() {
+        let instructions = parse("snd 1\nsnd 2\nsnd p\nrcv a\nrcv b\nrcv c\nrcv d\n");
+        assert_eq!(Ok(Some(3)), do_part2(&instructions, Settings::default()));
+    }
+
+    #[test]
+    fn part2_halting_program() {
+        // Program 0 sends twice and program 1 once; each receives a
+        // single value and then runs off the end
+        let instructions = parse("snd 5\njgz p 2\nsnd 6\nrcv a\n");
+        assert_eq!(Ok(Some(1)), do_part2(&instructions, Settings::default()));
+    }
+
+    #[test]
+    fn part1_arithmetic_policy() {
+        // Squaring 2 reaches 2^64, which only wraps around to zero
+        let instructions = parse("set a 2\nmul a a\njgz a -1\nsnd a\nrcv 1\n");
+        let e = do_part1(&instructions, Settings::default()).unwrap_err();
+        assert_eq!(vm::Fault::Overflow, e.fault);
+        assert_eq!(1, e.instruction_pointer);
+        assert_eq!(
+            Ok(ExitReason::Recovered(0)),
+            do_part1(&instructions, arithmetic(Arithmetic::Wrapping))
+        );
+    }
+
+    #[test]
+    fn part2_mod_by_zero() {
+        let instructions = parse("snd p\nrcv a\nmod a p\n");
+        let e = do_part2(&instructions, arithmetic(Arithmetic::Saturating)).unwrap_err();
+        assert_eq!(vm::Fault::DivideByZero, e.fault);
+    }
+
+    #[test]
+    fn part1_exit_reasons() {
+        let instructions = parse("snd 1\njgz 1 2\nrcv 1\n");
+        assert_eq!(Ok(ExitReason::Halted), do_part1(&instructions, Settings::default()));
+
+        // Loops forever without recovering anything
+        let instructions = parse("snd 1\nrcv 0\njgz 1 -1\n");
+        assert_eq!(Ok(ExitReason::StepLimit), do_part1(&instructions, step_limit(1000)));
+
+        let mut duet = Duet::new(instructions, Sound::new());
+        duet.set_step_limit(Some(10));
+        assert_eq!(Ok(ExitReason::StepLimit), duet.execute());
+        assert_eq!(2, duet.instruction_pointer());
+        assert_eq!(Ok(ExitReason::StepLimit), duet.execute());
+    }
+
+    #[test]
+    fn part2_discards_rcv_number() {
+        // The first value received is discarded, so a is 2 and program
+        // 1 sends only what it sends unconditionally
+        let puzzle = Puzzle::parse("snd 1\nsnd 2\nrcv 0\nrcv a\njgz a 2\nsnd 3\n").unwrap();
+        assert_eq!(Ok(()), puzzle.validate());
+        assert_eq!(Ok(Some("2".to_string())), puzzle.part2());
+    }
+
+    #[test]
+    fn unsupported_instructions_fail() {
+        let puzzle = Puzzle::parse("snd p\nsub a 1\nrcv a\njnz a -2\n").unwrap();
+        assert_eq!(
+            Err(SolveError::new(
+                "instruction 1 (sub a 1): not in the duet instruction set; \
+                 instruction 3 (jnz a -2): not in the duet instruction set"
+            )),
+            puzzle.part2()
+        );
+        assert!(puzzle.part1().is_err());
+    }
+
+    #[test]
+    fn topologies() {
+        assert_eq!(&[(0, 1), (1, 2), (2, 0)], Topology::ring(3).edges());
+        assert_eq!(6, Topology::fully_connected(3).edges().len());
+        assert_eq!(&[(0, 1), (1, 0), (0, 2), (2, 0)], Topology::star(3).edges());
+
+        let topology = Topology::parse_edges("0 -> 3\n3 -> 1\n").unwrap();
+        assert_eq!(4, topology.programs());
+        assert_eq!(&[(0, 3), (3, 1)], topology.edges());
+
+        let e = Topology::parse_edges("0 -> 1\n1 => 0\n").unwrap_err();
+        assert_eq!(2, e.line);
+        let e = Topology::parse_edges("0 -> x\n").unwrap_err();
+        assert_eq!((1, 6), (e.line, e.column));
+        let e = Topology::parse_edges("0 -> 1\n1000000000 -> 0\n").unwrap_err();
+        assert_eq!((2, 1), (e.line, e.column));
+        assert_eq!("program number too large: \"1000000000\"", e.message);
+    }
+
+    #[test]
+    fn network_reports() {
+        // Each program passes on what it receives, plus one, and halts
+        // on receiving a value over 5
+        let puzzle = Puzzle::parse(
+            "snd p\nrcv a\nset b a\nadd b -5\njgz b 4\nadd a 1\nsnd a\njgz 1 -6\n",
+        ).unwrap();
+        let reports = puzzle.network(&Topology::ring(3)).unwrap();
+        let counts: Vec<(u32, u32)> = reports.iter().map(|report| (report.sent, report.received)).collect();
+        assert_eq!(vec![(7, 6), (5, 5), (6, 5)], counts);
+        assert_eq!(ExitReason::Halted, reports[1].exit);
+
+        // The provided example, with program 0 sending to all of the
+        // others but receiving nothing
+        let puzzle = Puzzle::parse("snd 1\nsnd 2\nsnd p\nrcv a\nrcv b\nrcv c\nrcv d\n").unwrap();
+        let topology = Topology::parse_edges("0 -> 1\n0 -> 2\n1 -> 2\n").unwrap();
+        let reports = puzzle.network(&topology).unwrap();
+        let counts: Vec<(u32, u32)> = reports.iter().map(|report| (report.sent, report.received)).collect();
+        assert_eq!(vec![(3, 0), (3, 3), (3, 4)], counts);
+        assert_eq!(ExitReason::Halted, reports[2].exit);
+
+        let puzzle = Puzzle::parse("snd p\nrcv a\njnz a -2\n").unwrap();
+        assert_eq!(
+            Err(SolveError::new("instruction 2 (jnz a -2): not in the duet instruction set")),
+            puzzle.network(&Topology::ring(3))
+        );
+    }
+
+    #[test]
+    fn part2_step_limit() {
+        // Each program keeps sending to the other, so neither blocks
+        let instructions = parse("snd 1\nrcv a\njgz a -2\n");
+        assert_eq!(Ok(None), do_part2(&instructions, step_limit(1000)));
+    }
+}
@@ -0,0 +1,142 @@
+use duet::{Duet, ExitReason};
+use topology::Topology;
+
+use vm::{Arithmetic, Instruction, Messages, VmError};
+
+use std::collections::VecDeque;
+use std::fmt::{self, Display, Formatter};
+
+// How one program of the network fared
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Report {
+    pub program_id: usize,
+    pub sent: u32,
+    pub received: u32,
+    pub exit: ExitReason,
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:>7} {:>10} {:>10}  {:?}",
+            self.program_id, self.sent, self.received, self.exit
+        )
+    }
+}
+
+//
+// Runs a network of duet programs in turn on a single thread. Each
+// edge of the topology has a queue of the values sent along it. When
+// a program is given its turn, the values waiting on its incoming
+// edges are moved to its inbox, in the order of the edges, and it runs
+// until it blocks on rcv or halts. The values it sent are then queued
+// on each of its outgoing edges. The network is deadlocked once no
+// program can make progress: each has either halted, run out of
+// steps, or is blocked on rcv with nothing waiting for it.
+//
+pub struct Scheduler {
+    duets: Vec<Duet<Messages>>,
+    edges: Vec<(usize, usize)>,
+    queues: Vec<VecDeque<i64>>,
+    // Why each program last returned, once it has run
+    exits: Vec<Option<ExitReason>>,
+}
+
+impl Scheduler {
+    // Program n starts with n in register p
+    pub fn new(instructions: &[Instruction], topology: &Topology) -> Scheduler {
+        let duets: Vec<Duet<Messages>> = (0..topology.programs())
+            .map(|program_id| {
+                Duet::with_program_id(instructions.to_vec(), program_id as i64, Messages::new())
+            })
+            .collect();
+
+        Scheduler {
+            exits: vec![None; duets.len()],
+            duets,
+            edges: topology.edges().to_vec(),
+            queues: vec![VecDeque::new(); topology.edges().len()],
+        }
+    }
+
+    pub fn set_arithmetic(&mut self, arithmetic: Arithmetic) {
+        for duet in self.duets.iter_mut() {
+            duet.set_arithmetic(arithmetic);
+        }
+    }
+
+    pub fn set_step_limit(&mut self, step_limit: Option<u64>) {
+        for duet in self.duets.iter_mut() {
+            duet.set_step_limit(step_limit);
+        }
+    }
+
+    fn finished(exit: Option<ExitReason>) -> bool {
+        matches!(exit, Some(ExitReason::Halted) | Some(ExitReason::StepLimit))
+    }
+
+    fn waiting(&self, program_id: usize) -> bool {
+        self.duets[program_id].has_pending()
+            || self.edges
+                .iter()
+                .zip(self.queues.iter())
+                .any(|(&(_, to), queue)| to == program_id && !queue.is_empty())
+    }
+
+    fn deadlocked(&self) -> bool {
+        self.exits
+            .iter()
+            .enumerate()
+            .all(|(program_id, &exit)| match exit {
+                Some(ExitReason::Blocked) => !self.waiting(program_id),
+                exit => Scheduler::finished(exit),
+            })
+    }
+
+    fn turn(&mut self, program_id: usize) -> Result<(), VmError> {
+        for (edge, &(_, to)) in self.edges.iter().enumerate() {
+            if to == program_id {
+                for value in self.queues[edge].drain(..) {
+                    self.duets[program_id].deliver(value);
+                }
+            }
+        }
+
+        self.exits[program_id] = Some(self.duets[program_id].execute()?);
+
+        let sent = self.duets[program_id].take_sent();
+        for (edge, &(from, _)) in self.edges.iter().enumerate() {
+            if from == program_id {
+                self.queues[edge].extend(sent.iter().cloned());
+            }
+        }
+        Ok(())
+    }
+
+    //
+    // Returns how each program fared, or the first instruction any
+    // program failed to execute
+    //
+    pub fn run(&mut self) -> Result<Vec<Report>, VmError> {
+        while !self.deadlocked() {
+            for program_id in 0..self.duets.len() {
+                if !Scheduler::finished(self.exits[program_id]) {
+                    self.turn(program_id)?;
+                }
+            }
+        }
+
+        Ok(self.duets
+            .iter()
+            .zip(self.exits.iter())
+            .enumerate()
+            .map(|(program_id, (duet, exit))| Report {
+                program_id,
+                sent: duet.send_counter(),
+                received: duet.receive_counter(),
+                exit: exit.unwrap(),
+            })
+            .collect())
+    }
+}
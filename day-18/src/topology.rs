@@ -0,0 +1,87 @@
+use solver::{self, ParseError};
+
+// The most programs a network may have, each with its own machine
+pub const MAX_PROGRAMS: usize = 1000;
+
+//
+// Which duet programs send to which. Every value a program sends goes
+// to each program it has an edge to; a program with no edges out of
+// it sends into the void.
+//
+#[derive(Debug, Clone, PartialEq)]
+pub struct Topology {
+    programs: usize,
+    edges: Vec<(usize, usize)>,
+}
+
+impl Topology {
+    // Each program sends to the next, and the last to the first
+    pub fn ring(programs: usize) -> Topology {
+        Topology {
+            programs,
+            edges: (0..programs).map(|from| (from, (from + 1) % programs)).collect(),
+        }
+    }
+
+    // Each program sends to every other
+    pub fn fully_connected(programs: usize) -> Topology {
+        let mut edges = Vec::new();
+        for from in 0..programs {
+            for to in 0..programs {
+                if from != to {
+                    edges.push((from, to));
+                }
+            }
+        }
+        Topology { programs, edges }
+    }
+
+    // Program 0 and each of the others send to one another
+    pub fn star(programs: usize) -> Topology {
+        let mut edges = Vec::new();
+        for spoke in 1..programs {
+            edges.push((0, spoke));
+            edges.push((spoke, 0));
+        }
+        Topology { programs, edges }
+    }
+
+    //
+    // One edge per line, such as "2 -> 0" for program 2 sending to
+    // program 0. The programs are numbered from 0 up to the highest
+    // number mentioned, which must be below MAX_PROGRAMS.
+    //
+    pub fn parse_edges(input: &str) -> Result<Topology, ParseError> {
+        let edges = solver::parse_lines(input, parse_edge)?;
+        let programs = edges
+            .iter()
+            .map(|&(from, to)| from.max(to) + 1)
+            .max()
+            .unwrap_or(0);
+        Ok(Topology { programs, edges })
+    }
+
+    pub fn programs(&self) -> usize {
+        self.programs
+    }
+
+    pub fn edges(&self) -> &[(usize, usize)] {
+        &self.edges
+    }
+}
+
+fn parse_edge(line: &str) -> Result<(usize, usize), ParseError> {
+    let separator = match line.find("->") {
+        Some(separator) => separator,
+        None => return Err(ParseError::new(1, 1, line, "expected \"from -> to\"")),
+    };
+    let program = |token: &str| {
+        let token = token.trim();
+        match token.parse::<usize>() {
+            Ok(program) if program < MAX_PROGRAMS => Ok(program),
+            Ok(_) => Err(ParseError::token(1, line, token, "program number too large")),
+            Err(_) => Err(ParseError::token(1, line, token, "invalid program number")),
+        }
+    };
+    Ok((program(&line[..separator])?, program(&line[separator + 2..])?))
+}
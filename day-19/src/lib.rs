@@ -0,0 +1,123 @@
+extern crate solver;
+
+use std::iter::FromIterator;
+use solver::{Answer, ParseError, Solver};
+
+#[derive(Debug, PartialEq)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Debug)]
+struct State {
+    diagram: Vec<Vec<char>>,
+    row: usize,
+    column: usize,
+    direction: Direction,
+    letters: Vec<char>,
+    steps: u32,
+}
+
+impl State {
+    fn new(lines: Vec<&str>) -> State {
+        let mut diagram: Vec<Vec<char>> = Vec::new();
+        for line in lines.iter() {
+            diagram.push(line.chars().collect());
+        }
+        let mut column: usize = 0;
+        loop {
+            if diagram[0][column] == '|' {
+                break;
+            }
+            column += 1;
+        }
+
+        State {
+            diagram,
+            row: 0,
+            column,
+            direction: Direction::Down,
+            letters: Vec::new(),
+            steps: 0,
+        }
+    }
+
+    fn go(&mut self) {
+        loop {
+            match self.direction {
+                Direction::Up => {
+                    self.row -= 1;
+                }
+                Direction::Down => {
+                    self.row += 1;
+                }
+                Direction::Left => {
+                    self.column -= 1;
+                }
+                Direction::Right => {
+                    self.column += 1;
+                }
+            }
+            self.steps += 1;
+            match self.diagram[self.row][self.column] {
+                ' ' => {
+                    break;
+                }
+                '+' => {
+                    if self.direction == Direction::Up || self.direction == Direction::Down {
+                        if self.diagram[self.row][self.column + 1] == ' ' {
+                            self.direction = Direction::Left;
+                        } else {
+                            self.direction = Direction::Right;
+                        }
+                    } else if self.direction == Direction::Left
+                        || self.direction == Direction::Right
+                    {
+                        if self.diagram[self.row + 1][self.column] == ' ' {
+                            self.direction = Direction::Up;
+                        } else {
+                            self.direction = Direction::Down;
+                        }
+                    }
+                    //println!(
+                    //    "[{:3}, {:3}] turn {:?}",
+                    //    self.row, self.column, self.direction
+                    //);
+                }
+                c => {
+                    if c.is_alphabetic() {
+                        self.letters.push(c);
+                        //println!("[{:3}, {:3}] add letter {}", self.row, self.column, c);
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub struct Puzzle {
+    state: State,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let lines: Vec<&str> = input.lines().collect();
+
+        let mut state = State::new(lines);
+
+        state.go();
+
+        Ok(Puzzle { state })
+    }
+
+    fn part1(&self) -> Answer {
+        Ok(Some(String::from_iter(&self.state.letters)))
+    }
+
+    fn part2(&self) -> Answer {
+        Ok(Some(self.state.steps.to_string()))
+    }
+}
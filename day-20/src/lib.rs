@@ -0,0 +1,213 @@
+use std::fmt::{Display, Error, Formatter};
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+extern crate pest;
+#[macro_use]
+extern crate pest_derive;
+extern crate solver;
+
+use pest::Parser;
+use solver::{Answer, ParseError, Solver};
+
+#[derive(Parser)]
+#[grammar = "particle.pest"]
+struct ParticleParser;
+
+#[derive(Debug, Default, Eq, PartialEq, Hash, Clone)]
+struct Coordinates {
+    x: i64,
+    y: i64,
+    z: i64,
+}
+
+#[derive(Debug, Default, Clone)]
+struct Particle {
+    position: Coordinates,
+    velocity: Coordinates,
+    acceleration: Coordinates,
+}
+
+impl Particle {
+    fn new() -> Particle {
+        Particle::default()
+    }
+
+    fn tick(&mut self) -> Coordinates {
+        self.velocity.x += self.acceleration.x;
+        self.velocity.y += self.acceleration.y;
+        self.velocity.z += self.acceleration.z;
+
+        self.position.x += self.velocity.x;
+        self.position.y += self.velocity.y;
+        self.position.z += self.velocity.z;
+
+        self.position.clone()
+    }
+
+    fn manhattan_distance(&self) -> i64 {
+        self.position.x.abs() + self.position.y.abs() + self.position.z.abs()
+    }
+}
+
+impl Display for Particle {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(
+            f,
+            "p=<{},{},{}>, v=<{},{},{}>, a=<{},{},{}>",
+            self.position.x,
+            self.position.y,
+            self.position.z,
+            self.velocity.x,
+            self.velocity.y,
+            self.velocity.z,
+            self.acceleration.x,
+            self.acceleration.y,
+            self.acceleration.z
+        )
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    particles: Vec<Particle>,
+    collision_detector: HashMap<Coordinates, Vec<usize>>,
+}
+
+impl State {
+    fn new(particles: Vec<Particle>) -> State {
+        State {
+            particles,
+            collision_detector: HashMap::new(),
+        }
+    }
+
+    fn tick(&mut self) -> usize {
+        self.collision_detector = HashMap::new();
+
+        for (index, particle) in self.particles.iter_mut().enumerate() {
+            let position = particle.tick();
+
+            match self.collision_detector.entry(position) {
+                Entry::Vacant(vacant) => {
+                    vacant.insert(vec![index]);
+                }
+                Entry::Occupied(mut occupied) => {
+                    occupied.get_mut().push(index);
+                }
+            }
+        }
+
+        // Check for collisions and remove affected particles
+        let mut collided: Vec<usize> = Vec::new();
+        for indices in self.collision_detector.values() {
+            if indices.len() > 1 {
+                collided.extend(indices);
+            }
+        }
+        collided.sort();
+        collided.reverse();
+        for index in collided.iter() {
+            self.particles.remove(*index);
+        }
+        collided.len()
+    }
+
+    fn closest(&self) -> usize {
+        let (index, _particle) = self.particles
+            .iter()
+            .enumerate()
+            .min_by(|x, y| x.1.manhattan_distance().cmp(&y.1.manhattan_distance()))
+            .unwrap();
+        //println!("[{}] {}", index, _particle);
+        index
+    }
+
+    fn particle_count(&self) -> usize {
+        self.particles.len()
+    }
+}
+
+fn parse_line(line: &str) -> Result<Particle, ParseError> {
+    let pairs = ParticleParser::parse(Rule::particle, line).map_err(ParseError::from_pest)?;
+
+    let mut particle = Particle::new();
+
+    for pair in pairs {
+        let rule = pair.as_rule();
+        let text = pair.clone().into_span().as_str();
+        let number = text.parse().map_err(|e| ParseError::token(1, line, text, e));
+        match rule {
+            Rule::px => {
+                particle.position.x = number?;
+            }
+            Rule::py => {
+                particle.position.y = number?;
+            }
+            Rule::pz => {
+                particle.position.z = number?;
+            }
+            Rule::vx => {
+                particle.velocity.x = number?;
+            }
+            Rule::vy => {
+                particle.velocity.y = number?;
+            }
+            Rule::vz => {
+                particle.velocity.z = number?;
+            }
+            Rule::ax => {
+                particle.acceleration.x = number?;
+            }
+            Rule::ay => {
+                particle.acceleration.y = number?;
+            }
+            Rule::az => {
+                particle.acceleration.z = number?;
+            }
+            _ => {
+                unimplemented!("parse_line");
+            }
+        }
+    }
+
+    Ok(particle)
+}
+
+// This arbitrarily selected number of repetitions yields
+// the correct answer for part 1 and for part 2
+const REPETITIONS: u32 = 500;
+
+pub struct Puzzle {
+    particles: Vec<Particle>,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        Ok(Puzzle {
+            particles: solver::parse_lines(input, parse_line)?,
+        })
+    }
+
+    fn part1(&self) -> Answer {
+        // Leave collided particles in place so that indices are stable
+        let mut particles = self.particles.clone();
+        for _ in 0..REPETITIONS {
+            for particle in particles.iter_mut() {
+                particle.tick();
+            }
+        }
+
+        let state = State::new(particles);
+        Ok(Some(state.closest().to_string()))
+    }
+
+    fn part2(&self) -> Answer {
+        let mut state = State::new(self.particles.clone());
+
+        for _ in 0..REPETITIONS {
+            state.tick();
+        }
+        Ok(Some(state.particle_count().to_string()))
+    }
+}
@@ -0,0 +1,113 @@
+//
+// Runs the part 2 program by brute force, without the optimizer, on
+// the machine's compiled bytecode and on a plain interpreter which
+// keeps its registers in a HashMap, as the machine used to.
+//
+//     cargo bench -p day-23
+//
+// The real range of candidates would take hours, so the program is
+// cut down to test eleven candidates around 1000.
+//
+extern crate solver;
+extern crate vm;
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use vm::{parse_line, Instruction, Machine, Operand, Silent};
+
+const PROGRAM: &str = "set b 79
+set c b
+jnz a 2
+jnz 1 5
+mul b 10
+sub b -200
+set c b
+sub c -170
+set f 1
+set d 2
+set e 2
+set g d
+mul g e
+sub g b
+jnz g 2
+set f 0
+sub e -1
+set g e
+sub g b
+jnz g -8
+sub d -1
+set g d
+sub g b
+jnz g -13
+jnz f 2
+sub h -1
+set g b
+sub g c
+jnz g 2
+jnz 1 3
+sub b -17
+jnz 1 -23
+";
+
+fn interpret(code: &[Instruction]) -> i64 {
+    let mut registers: HashMap<char, i64> = HashMap::new();
+    registers.insert('a', 1);
+    let value = |registers: &HashMap<char, i64>, operand| match operand {
+        Operand::Reg(register) => *registers.get(&register).unwrap_or(&0),
+        Operand::Imm(number) => number,
+    };
+
+    let mut instruction_pointer: i64 = 0;
+    while instruction_pointer >= 0 && instruction_pointer < code.len() as i64 {
+        let mut offset = 1;
+        match code[instruction_pointer as usize] {
+            Instruction::Set(register, y) => {
+                let y = value(&registers, y);
+                registers.insert(register, y);
+            }
+            Instruction::Sub(register, y) => {
+                let y = value(&registers, y);
+                *registers.entry(register).or_insert(0) -= y;
+            }
+            Instruction::Mul(register, y) => {
+                let y = value(&registers, y);
+                *registers.entry(register).or_insert(0) *= y;
+            }
+            Instruction::Jnz(x, y) => {
+                if value(&registers, x) != 0 {
+                    offset = value(&registers, y);
+                }
+            }
+            instruction => panic!("not used by day 23: {}", instruction),
+        }
+        instruction_pointer += offset;
+    }
+    *registers.get(&'h').unwrap_or(&0)
+}
+
+fn compiled(code: &[Instruction]) -> i64 {
+    let mut machine = Machine::new(code.to_vec(), Silent);
+    machine.set_register('a', 1);
+    machine.run().unwrap();
+    machine.register('h')
+}
+
+fn time<F: Fn(&[Instruction]) -> i64>(name: &str, code: &[Instruction], f: F) -> Duration {
+    let start = Instant::now();
+    let h = f(code);
+    let elapsed = start.elapsed();
+    println!("{:>10}: h = {}, {:?}", name, h, elapsed);
+    elapsed
+}
+
+fn main() {
+    let code = solver::parse_lines(PROGRAM, parse_line).unwrap();
+
+    let hash_map = time("hash map", &code, interpret);
+    let bytecode = time("bytecode", &code, compiled);
+    println!(
+        "   speedup: {:.1}x",
+        hash_map.as_secs_f64() / bytecode.as_secs_f64()
+    );
+}
@@ -1,140 +1,115 @@
-use instruction::Instruction;
+use optimizer::{self, CompositeLoop};
+use profiler::Profile;
 
-use std::collections::HashMap;
+use vm::{Debuggee, Instruction, Machine, Silent, Status, Tracer, VmError};
 
+//
+// The coprocessor runs the shared register machine, with registers
+// a through h, no use for snd or rcv, and a count of mul instructions
+// executed.
+//
 #[derive(Debug)]
 pub struct Coprocessor {
-    code_segment: Vec<Instruction>,
-    instruction_pointer: i64,
-    registers: HashMap<char, i64>,
-    mul_count: u32,
+    machine: Machine<Silent>,
+    mul_count: u64,
+    composite_loops: Vec<CompositeLoop>,
+    profile: Option<Profile>,
 }
 
 impl Coprocessor {
     pub fn new(instructions: Vec<Instruction>) -> Coprocessor {
-        let mut registers: HashMap<char, i64> = HashMap::new();
-        for letter in "abcdefgh".chars() {
-            registers.insert(letter, 0);
-        }
+        let mut machine = Machine::new(instructions, Silent);
+        machine.limit_registers("abcdefgh");
 
         Coprocessor {
-            code_segment: instructions,
-            instruction_pointer: 0,
-            registers: registers,
+            machine,
             mul_count: 0,
+            composite_loops: Vec::new(),
+            profile: None,
         }
     }
 
+    // Look for loops which can be run in closed form
+    pub fn optimize(&mut self) {
+        self.composite_loops = optimizer::find_composite_loops(self.machine.code_segment());
+    }
+
+    // Start counting the instructions executed from here on
+    pub fn enable_profiling(&mut self) {
+        self.profile = Some(Profile::new(self.machine.code_segment()));
+    }
+
+    pub fn profile(&self) -> Option<&Profile> {
+        self.profile.as_ref()
+    }
+
     pub fn set_register(&mut self, register: char, new_value: i64) {
-        if let Some(value) = self.registers.get_mut(&register) {
-            *value = new_value;
-        }
+        self.machine.set_register(register, new_value);
     }
 
-    pub fn execute(&mut self, part_2_flag: bool) {
-        let mut jumped: bool = false;
-        loop {
-            if self.instruction_pointer as usize >= self.code_segment.len() {
-                if part_2_flag {
-                    if let Some(value) = self.registers.get(&'h') {
-                        println!("part 2: register h contains {}", *value);
-                        return;
-                    }
-                } else {
-                    println!("part 1: mul count = {}", self.mul_count);
-                    return;
-                }
-            }
-            match self.code_segment[self.instruction_pointer as usize] {
-                Instruction::NoOp => {}
-                Instruction::SetR(register1, register2) => {
-                    let mut new_value = 0;
-                    if let Some(value) = self.registers.get(&register2) {
-                        new_value = *value;
-                    }
-                    if let Some(value) = self.registers.get_mut(&register1) {
-                        *value = new_value;
-                    }
-                }
-                Instruction::SetN(register, number) => {
-                    if let Some(value) = self.registers.get_mut(&register) {
-                        *value = number;
-                    };
-                }
-                Instruction::SubR(register1, register2) => {
-                    let mut minuend = 0;
-                    if let Some(value) = self.registers.get(&register2) {
-                        minuend = *value;
-                    }
-                    if let Some(value) = self.registers.get_mut(&register1) {
-                        *value -= minuend;
-                    }
-                }
-                Instruction::SubN(register, number) => {
-                    if let Some(value) = self.registers.get_mut(&register) {
-                        *value -= number;
-                    }
-                }
-                Instruction::MulR(register1, register2) => {
-                    let mut factor = 0;
-                    if let Some(value) = self.registers.get(&register2) {
-                        factor = *value;
-                    }
-                    if let Some(value) = self.registers.get_mut(&register1) {
-                        *value *= factor;
-                        self.mul_count += 1;
-                    }
-                }
-                Instruction::MulN(register, number) => {
-                    if let Some(value) = self.registers.get_mut(&register) {
-                        *value *= number;
-                        self.mul_count += 1;
-                    }
-                }
-                Instruction::JnzRR(register1, register2) => {
-                    let mut x = 0;
-                    if let Some(value) = self.registers.get(&register1) {
-                        x = *value;
-                    }
-                    if let Some(offset) = self.registers.get(&register2) {
-                        if x != 0 {
-                            self.instruction_pointer += *offset;
-                            jumped = true;
-                        }
-                    }
-                }
-                Instruction::JnzRN(register, number) => {
-                    let mut x = 0;
-                    if let Some(value) = self.registers.get(&register) {
-                        x = *value;
-                    }
-                    if x != 0 {
-                        self.instruction_pointer += number;
-                        jumped = true;
-                    }
-                }
-                Instruction::JnzNR(number, register) => {
-                    if let Some(offset) = self.registers.get(&register) {
-                        if number != 0 {
-                            self.instruction_pointer += *offset;
-                            jumped = true;
-                        }
-                    }
-                }
-                Instruction::JnzNN(number1, number2) => {
-                    if number1 != 0 {
-                        self.instruction_pointer += number2;
-                        jumped = true;
-                    }
+    pub fn register(&self, register: char) -> i64 {
+        self.machine.register(register)
+    }
+
+    pub fn mul_count(&self) -> u64 {
+        self.mul_count
+    }
+
+    // Execute one instruction, or a whole composite loop when the
+    // optimizer has found one here
+    pub fn step(&mut self) -> Result<Status, VmError> {
+        let ip = self.machine.instruction_pointer();
+        if let Some(composite_loop) = self.composite_loops
+            .iter()
+            .find(|composite_loop| composite_loop.start() as i64 == ip)
+        {
+            if let Some(mul_count) = composite_loop.shortcut(&mut self.machine) {
+                self.mul_count += mul_count;
+                if let Some(ref mut profile) = self.profile {
+                    profile.record_shortcut(composite_loop.start());
                 }
+                return Ok(Status::Running);
             }
-            if jumped == false {
-                self.instruction_pointer += 1;
-            }
-            jumped = false;
-            // Since the puzzle presumes that rcv will be executed, ignore
-            // the possibility of continuing or jumping off either end
-            // of the progam.
         }
+        let instruction = match self.machine.current_instruction() {
+            Some(&instruction) => instruction,
+            None => return Ok(Status::Halted),
+        };
+        let status = self.machine.step()?;
+        if let Instruction::Mul(_, _) = instruction {
+            self.mul_count += 1;
+        }
+        if let Some(ref mut profile) = self.profile {
+            profile.record(ip as usize);
+        }
+        Ok(status)
+    }
+
+    // Run until the program runs off the end
+    pub fn execute(&mut self) -> Result<(), VmError> {
+        while self.step()? == Status::Running {}
+        Ok(())
+    }
+}
+
+impl Debuggee for Coprocessor {
+    fn step(&mut self) -> Result<Status, VmError> {
+        Coprocessor::step(self)
+    }
+
+    fn instruction_pointer(&self) -> i64 {
+        self.machine.instruction_pointer()
+    }
+
+    fn current_instruction(&self) -> Option<Instruction> {
+        self.machine.current_instruction().cloned()
+    }
+
+    fn registers(&self) -> Vec<(char, i64)> {
+        self.machine.registers()
+    }
+
+    fn set_tracer(&mut self, tracer: Option<Box<dyn Tracer>>) {
+        self.machine.set_tracer(tracer);
     }
 }
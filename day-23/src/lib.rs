@@ -0,0 +1,255 @@
+extern crate solver;
+extern crate vm;
+
+mod coprocessor;
+mod optimizer;
+mod profiler;
+
+use coprocessor::Coprocessor;
+pub use profiler::Profile;
+use solver::{Answer, ParseError, Part, SolveError, Solver};
+use vm::{parse_line, Debugger, Instruction, VmError};
+
+use std::io::{self, BufRead, Write};
+
+fn do_part1(instructions: &[Instruction]) -> Result<u64, VmError> {
+    let mut coprocessor = Coprocessor::new(instructions.to_vec());
+    coprocessor.execute()?;
+    Ok(coprocessor.mul_count())
+}
+
+// Naive brute force approach; takes a very long time to finish.
+#[allow(dead_code)]
+fn do_part2_brute_force(instructions: &[Instruction]) -> Result<i64, VmError> {
+    let mut coprocessor = Coprocessor::new(instructions.to_vec());
+    coprocessor.set_register('a', 1);
+    coprocessor.execute()?;
+    Ok(coprocessor.register('h'))
+}
+
+// The program counts composite numbers the slow way; run its inner
+// loops in closed form instead.
+fn do_part2(instructions: &[Instruction]) -> Result<i64, VmError> {
+    let mut coprocessor = Coprocessor::new(instructions.to_vec());
+    coprocessor.optimize();
+    coprocessor.set_register('a', 1);
+    coprocessor.execute()?;
+    Ok(coprocessor.register('h'))
+}
+
+fn answer<T: ToString>(result: Result<T, VmError>) -> Answer {
+    result
+        .map(|answer| Some(answer.to_string()))
+        .map_err(SolveError::new)
+}
+
+pub struct Puzzle {
+    instructions: Vec<Instruction>,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        Ok(Puzzle {
+            instructions: solver::parse_lines(input, parse_line)?,
+        })
+    }
+
+    fn part1(&self) -> Answer {
+        answer(do_part1(&self.instructions))
+    }
+
+    fn part2(&self) -> Answer {
+        answer(do_part2(&self.instructions))
+    }
+}
+
+impl Puzzle {
+    // The program as an annotated assembly listing
+    pub fn disassemble(&self) -> String {
+        vm::disassemble(&self.instructions)
+    }
+
+    //
+    // Step through the program under the debugger, reading commands
+    // from input. Part 2 sets register a to 1 but leaves the
+    // optimizer off, so that every instruction can be seen.
+    //
+    pub fn debug<R: BufRead, W: Write>(&self, part: Part, input: R, output: W) -> io::Result<()> {
+        let mut coprocessor = Coprocessor::new(self.instructions.clone());
+        if part == Part::Two {
+            coprocessor.set_register('a', 1);
+        }
+        Debugger::new().run(&mut coprocessor, input, output)?;
+        Ok(())
+    }
+
+    //
+    // Run either part with profiling on. Part 2 keeps the optimizer,
+    // so the profile shows how often each loop was run in closed form.
+    //
+    pub fn profile(&self, part: Part) -> Result<Profile, VmError> {
+        let mut coprocessor = Coprocessor::new(self.instructions.clone());
+        if part == Part::Two {
+            coprocessor.optimize();
+            coprocessor.set_register('a', 1);
+        }
+        coprocessor.enable_profiling();
+        coprocessor.execute()?;
+        Ok(coprocessor.profile().unwrap().clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM: &str = "set b 79
+set c b
+jnz a 2
+jnz 1 5
+mul b 100
+sub b -100000
+set c b
+sub c -17000
+set f 1
+set d 2
+set e 2
+set g d
+mul g e
+sub g b
+jnz g 2
+set f 0
+sub e -1
+set g e
+sub g b
+jnz g -8
+sub d -1
+set g d
+sub g b
+jnz g -13
+jnz f 2
+sub h -1
+set g b
+sub g c
+jnz g 2
+jnz 1 3
+sub b -17
+jnz 1 -23
+";
+
+    fn parse(input: &str) -> Vec<Instruction> {
+        solver::parse_lines(input, parse_line).unwrap()
+    }
+
+    // Run with a = 0, checking a single b, with and without the
+    // optimizer
+    fn run(instructions: &[Instruction], optimize: bool) -> (u64, i64) {
+        let mut coprocessor = Coprocessor::new(instructions.to_vec());
+        if optimize {
+            coprocessor.optimize();
+        }
+        coprocessor.execute().unwrap();
+        (coprocessor.mul_count(), coprocessor.register('h'))
+    }
+
+    #[test]
+    fn finds_composite_loop() {
+        let loops = optimizer::find_composite_loops(&parse(PROGRAM));
+        assert_eq!(1, loops.len());
+        assert_eq!(10, loops[0].start());
+    }
+
+    #[test]
+    fn optimizer_matches_execution() {
+        for b in 3..60 {
+            let program = PROGRAM.replacen("set b 79", &format!("set b {}", b), 1);
+            let instructions = parse(&program);
+            assert_eq!(run(&instructions, false), run(&instructions, true), "b = {}", b);
+        }
+    }
+
+    #[test]
+    fn part1_mul_count() {
+        assert_eq!(Ok(5929), do_part1(&parse(PROGRAM)));
+    }
+
+    #[test]
+    fn part2_counts_composites() {
+        assert_eq!(Ok(907), do_part2(&parse(PROGRAM)));
+    }
+
+    #[test]
+    fn debug_session() {
+        let puzzle = Puzzle::parse(PROGRAM).unwrap();
+        let mut output = Vec::new();
+        puzzle
+            .debug(Part::Two, "break 8\ncontinue\nprint b\nprint c\n".as_bytes(), &mut output)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("b = 107900\n"));
+        assert!(output.contains("c = 124900\n"));
+    }
+
+    #[test]
+    fn profile_part1() {
+        let puzzle = Puzzle::parse(PROGRAM).unwrap();
+        let profile = puzzle.profile(Part::One).unwrap();
+        assert_eq!(5929, profile.count(12));
+        assert_eq!(("mul", 5929), profile.opcode_counts()[3]);
+        assert_eq!(11..15, profile.hottest_blocks()[0].0);
+        assert_eq!((11, 5929), profile.hottest_instructions()[0]);
+
+        let mut csv = Vec::new();
+        profile.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.starts_with("index,opcode,block,count\n0,set,0,1\n"));
+        assert!(csv.contains("\n12,mul,11,5929\n"));
+    }
+
+    #[test]
+    fn profile_part2_shortcuts() {
+        let puzzle = Puzzle::parse(PROGRAM).unwrap();
+        let profile = puzzle.profile(Part::Two).unwrap();
+        assert_eq!(1001, profile.shortcuts(10));
+        assert_eq!(0, profile.count(12));
+    }
+
+    #[test]
+    fn listing_round_trip() {
+        for line in PROGRAM.lines() {
+            assert_eq!(line, parse_line(line).unwrap().to_string());
+        }
+        let listing = Puzzle::parse(PROGRAM).unwrap().disassemble();
+        assert!(listing.contains("; block 0 -> block 2, block 1\n"));
+        assert!(listing.contains("L10:    10  set e 2\n"));
+        assert!(listing.contains("        31  jnz 1 -23  ; L8\n"));
+    }
+
+    #[test]
+    fn shortcut_declines_overflowing_products() {
+        let instructions = parse(PROGRAM);
+        let loops = optimizer::find_composite_loops(&instructions);
+        for &b in &[4_000_000_000, i64::MAX] {
+            let mut machine = vm::Machine::new(instructions.clone(), vm::Silent);
+            machine.set_instruction_pointer(10);
+            machine.set_register('b', b);
+            machine.set_register('d', 2);
+            assert_eq!(None, loops[0].shortcut(&mut machine), "b = {}", b);
+            assert_eq!(10, machine.instruction_pointer());
+        }
+    }
+
+    #[test]
+    fn part2_other_input() {
+        let program = PROGRAM.replacen("set b 79", "set b 65", 1);
+        assert_eq!(Ok(917), do_part2(&parse(&program)));
+    }
+
+    #[test]
+    fn only_registers_a_to_h() {
+        let program = PROGRAM.replacen("sub h -1", "sub z -1", 1);
+        let e = do_part2(&parse(&program)).unwrap_err();
+        assert_eq!(vm::Fault::UnknownRegister('z'), e.fault);
+        assert_eq!(25, e.instruction_pointer);
+    }
+}
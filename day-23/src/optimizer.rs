@@ -0,0 +1,144 @@
+//
+// The part 2 program counts the composite numbers in a range, testing
+// each candidate b with a pair of nested loops which try every product
+// d * e for d and e in 2..b. That takes about b^2 steps per candidate.
+// This pass finds those loops in the code segment, whatever registers
+// they use, so the coprocessor can skip straight to their outcome.
+//
+use vm::{Instruction, Io, Machine};
+use vm::Instruction::{Jnz, Mul, Set, Sub};
+use vm::Operand::{Imm, Reg};
+
+// The nested loops, as they appear in the puzzle input:
+//
+//      set e 2
+//      set g d
+//      mul g e
+//      sub g b
+//      jnz g 2
+//      set f 0
+//      sub e -1
+//      set g e
+//      sub g b
+//      jnz g -8
+//      sub d -1
+//      set g d
+//      sub g b
+//      jnz g -13
+//
+const LENGTH: usize = 14;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompositeLoop {
+    start: usize,
+    b: char,
+    d: char,
+    e: char,
+    f: char,
+    g: char,
+}
+
+impl CompositeLoop {
+    fn recognise(code: &[Instruction], start: usize) -> Option<CompositeLoop> {
+        let code = code.get(start..start + LENGTH)?;
+
+        let (e, g, d, b, f) = match (code[0], code[1], code[3], code[5]) {
+            (Set(e, Imm(2)), Set(g, Reg(d)), Sub(_, Reg(b)), Set(f, Imm(0))) => (e, g, d, b, f),
+            _ => return None,
+        };
+
+        let expected = [
+            Set(e, Imm(2)),
+            Set(g, Reg(d)),
+            Mul(g, Reg(e)),
+            Sub(g, Reg(b)),
+            Jnz(Reg(g), Imm(2)),
+            Set(f, Imm(0)),
+            Sub(e, Imm(-1)),
+            Set(g, Reg(e)),
+            Sub(g, Reg(b)),
+            Jnz(Reg(g), Imm(-8)),
+            Sub(d, Imm(-1)),
+            Set(g, Reg(d)),
+            Sub(g, Reg(b)),
+            Jnz(Reg(g), Imm(-13)),
+        ];
+        if code != &expected[..] {
+            return None;
+        }
+
+        // Each role needs a register of its own
+        let mut registers = vec![b, d, e, f, g];
+        registers.sort();
+        registers.dedup();
+        if registers.len() != 5 {
+            return None;
+        }
+
+        Some(CompositeLoop { start, b, d, e, f, g })
+    }
+
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    //
+    // If the machine is at the start of the loops, leave the
+    // registers as running the loops would have, move past them and
+    // return how many mul instructions that saved. Returns None,
+    // leaving the machine untouched, when the loops would not end
+    // normally; they are then best left to run as written.
+    //
+    pub fn shortcut<I: Io>(&self, machine: &mut Machine<I>) -> Option<u64> {
+        if machine.instruction_pointer() != self.start as i64 {
+            return None;
+        }
+
+        let b = machine.register(self.b);
+        let d = machine.register(self.d);
+
+        // Both loops compare with b only after counting up, so they
+        // end only if they start below b
+        if b <= 2 || d >= b {
+            return None;
+        }
+
+        // Each inner loop executes mul b - 2 times
+        let mul_count = (b - d).checked_mul(b - 2)?;
+
+        // The largest product the loops compute is (b - 1)^2. Should
+        // that not fit in an i64, the loops as written would fault or
+        // wrap, depending on the arithmetic policy, so run them instead.
+        (b - 1).checked_mul(b - 1)?;
+
+        // Does b have a divisor of at least d whose cofactor is in
+        // 2..b? Any divisor k of b below its square root pairs with
+        // cofactor b / k, and either may be the one tried as d.
+        let low = d.max(2);
+        let mut composite = false;
+        let mut k = 2;
+        while k <= b / k {
+            if b % k == 0 && (k >= low || b / k >= low) {
+                composite = true;
+                break;
+            }
+            k += 1;
+        }
+
+        if composite {
+            machine.set_register(self.f, 0);
+        }
+        machine.set_register(self.d, b);
+        machine.set_register(self.e, b);
+        machine.set_register(self.g, 0);
+        machine.set_instruction_pointer((self.start + LENGTH) as i64);
+
+        Some(mul_count as u64)
+    }
+}
+
+pub fn find_composite_loops(code: &[Instruction]) -> Vec<CompositeLoop> {
+    (0..code.len())
+        .filter_map(|start| CompositeLoop::recognise(code, start))
+        .collect()
+}
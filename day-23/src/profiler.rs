@@ -0,0 +1,145 @@
+use vm::{basic_blocks, Instruction};
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Write};
+use std::ops::Range;
+
+//
+// Execution counts gathered while the coprocessor runs: how often each
+// instruction was executed, and how often each composite loop was run
+// in closed form instead. Instructions skipped by a shortcut are not
+// counted.
+//
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profile {
+    code: Vec<Instruction>,
+    counts: Vec<u64>,
+    shortcuts: BTreeMap<usize, u64>,
+}
+
+impl Profile {
+    pub fn new(code: &[Instruction]) -> Profile {
+        Profile {
+            code: code.to_vec(),
+            counts: vec![0; code.len()],
+            shortcuts: BTreeMap::new(),
+        }
+    }
+
+    pub fn record(&mut self, index: usize) {
+        self.counts[index] += 1;
+    }
+
+    pub fn record_shortcut(&mut self, start: usize) {
+        *self.shortcuts.entry(start).or_insert(0) += 1;
+    }
+
+    pub fn count(&self, index: usize) -> u64 {
+        self.counts[index]
+    }
+
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    // How often the composite loop starting at index was skipped
+    pub fn shortcuts(&self, start: usize) -> u64 {
+        *self.shortcuts.get(&start).unwrap_or(&0)
+    }
+
+    // Instruction indexes with their counts, most executed first
+    pub fn hottest_instructions(&self) -> Vec<(usize, u64)> {
+        let mut instructions: Vec<(usize, u64)> = self.counts.iter().cloned().enumerate().collect();
+        instructions.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        instructions
+    }
+
+    // Cumulative counts for each opcode used, most executed first
+    pub fn opcode_counts(&self) -> Vec<(&'static str, u64)> {
+        let mut totals: BTreeMap<&'static str, u64> = BTreeMap::new();
+        for (instruction, count) in self.code.iter().zip(self.counts.iter()) {
+            *totals.entry(instruction.mnemonic()).or_insert(0) += count;
+        }
+        let mut opcodes: Vec<(&'static str, u64)> = totals.into_iter().collect();
+        opcodes.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        opcodes
+    }
+
+    // Basic blocks with the instructions executed within them, most
+    // executed first
+    pub fn hottest_blocks(&self) -> Vec<(Range<usize>, u64)> {
+        let mut blocks: Vec<(Range<usize>, u64)> = basic_blocks(&self.code)
+            .into_iter()
+            .map(|block| {
+                let count = self.counts[block.clone()].iter().sum();
+                (block, count)
+            })
+            .collect();
+        blocks.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.start.cmp(&b.0.start)));
+        blocks
+    }
+
+    // One line per instruction, in program order
+    pub fn write_csv<W: Write>(&self, mut output: W) -> io::Result<()> {
+        writeln!(output, "index,opcode,block,count")?;
+        for block in basic_blocks(&self.code) {
+            for index in block.clone() {
+                writeln!(
+                    output,
+                    "{},{},{},{}",
+                    index,
+                    self.code[index].mnemonic(),
+                    block.start,
+                    self.counts[index]
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for Profile {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let total = self.total();
+        let percent = |count: u64| {
+            if total == 0 {
+                0.0
+            } else {
+                count as f64 * 100.0 / total as f64
+            }
+        };
+
+        writeln!(f, "{:>5} {:>14} {:>7}  instruction", "index", "count", "%")?;
+        for (index, count) in self.hottest_instructions() {
+            writeln!(
+                f,
+                "{:>5} {:>14} {:>6.2}%  {}",
+                index,
+                count,
+                percent(count),
+                self.code[index]
+            )?;
+        }
+
+        writeln!(f, "\n{:>5} {:>14} {:>7}", "op", "count", "%")?;
+        for (opcode, count) in self.opcode_counts() {
+            writeln!(f, "{:>5} {:>14} {:>6.2}%", opcode, count, percent(count))?;
+        }
+
+        writeln!(f, "\n{:>9} {:>14} {:>7}", "block", "count", "%")?;
+        for (block, count) in self.hottest_blocks() {
+            let range = format!("{}..{}", block.start, block.end);
+            writeln!(f, "{:>9} {:>14} {:>6.2}%", range, count, percent(count))?;
+        }
+
+        if !self.shortcuts.is_empty() {
+            writeln!(f, "\ncomposite loops run in closed form")?;
+            for (start, count) in &self.shortcuts {
+                writeln!(f, "{:>5} {:>14}", start, count)?;
+            }
+        }
+
+        write!(f, "\n{} instructions executed", total)
+    }
+}
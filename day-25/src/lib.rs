@@ -0,0 +1,184 @@
+use std::collections::{HashMap, HashSet};
+
+extern crate pest;
+#[macro_use]
+extern crate pest_derive;
+extern crate solver;
+
+use pest::Parser;
+use solver::{Answer, ParseError, Solver};
+
+#[derive(Parser)]
+#[grammar = "blueprint.pest"]
+struct BlueprintParser;
+
+#[derive(Debug, Clone, Default)]
+enum Direction {
+    #[default]
+    Left,
+    Right,
+}
+
+#[derive(Debug, Default, Clone)]
+struct Rules {
+    write: u8,
+    direction: Direction,
+    next_state: char,
+}
+
+#[derive(Debug, Default, Clone)]
+struct State {
+    id: char,
+    on_zero: Rules,
+    on_one: Rules,
+}
+
+#[derive(Debug, Default, Clone)]
+struct Machine {
+    step_limit: u64,
+    step_count: u64,
+    states: HashMap<char, State>,
+    current_state: char,
+    tape: HashSet<i64>,
+    cursor: i64,
+}
+
+impl Machine {
+    fn new() -> Machine {
+        Machine::default()
+    }
+
+    fn parse(&mut self, lines: Vec<&str>) -> Result<(), ParseError> {
+        let mut state: State = State::default();
+        let mut state_zero_flag: bool = true;
+
+        for (index, line) in lines.into_iter().enumerate() {
+            let pairs = BlueprintParser::parse(Rule::line, line)
+                .map_err(|e| ParseError::from_pest(e).at_line(index + 1))?;
+
+            for pair in pairs {
+                let rule = pair.as_rule();
+                let span = pair.clone().into_span();
+                let text = span.as_str().to_string();
+                match rule {
+                    Rule::begin_state => {
+                        self.current_state = text.chars().next().unwrap();
+                    }
+                    Rule::step_limit => {
+                        self.step_limit = text.parse().map_err(|e| {
+                            ParseError::token(index + 1, line, span.as_str(), e)
+                        })?;
+                    }
+                    Rule::state_id => {
+                        state.id = text.chars().next().unwrap();
+                    }
+                    Rule::current_value => {
+                        state_zero_flag = text == "0";
+                    }
+                    Rule::write_value => {
+                        if state_zero_flag {
+                            state.on_zero.write = text.parse().unwrap();
+                        } else {
+                            state.on_one.write = text.parse().unwrap();
+                        }
+                    }
+                    Rule::move_direction => {
+                        let direction = match text.as_str() {
+                            "left" => Direction::Left,
+                            "right" => Direction::Right,
+                            _ => unimplemented!(""),
+                        };
+                        if state_zero_flag {
+                            state.on_zero.direction = direction;
+                        } else {
+                            state.on_one.direction = direction;
+                        }
+                    }
+                    Rule::next_state => {
+                        let next_state = text.chars().next().unwrap();
+                        if state_zero_flag {
+                            state.on_zero.next_state = next_state;
+                        } else {
+                            state.on_one.next_state = next_state;
+                            self.states.insert(state.id, state);
+                            state = State::default();
+                        }
+                    }
+                    _ => {
+                        unimplemented!("parse");
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn step(&mut self) {
+        
+        let cursor_on_zero: bool = !self.tape.contains(&self.cursor);
+        let rules: Rules;
+        if let Some(state) = self.states.get(&self.current_state) {
+            if cursor_on_zero {
+                rules = state.on_zero.clone();
+            } else {
+                rules = state.on_one.clone();
+            }
+        } else {
+            println!(
+                "Current state {} unknown, cannot determine rules",
+                self.current_state
+            );
+            return;
+        }
+        if rules.write == 0 {
+            self.tape.remove(&self.cursor);
+        } else {
+            self.tape.insert(self.cursor);
+        }
+        match rules.direction {
+            Direction::Left => {
+                self.cursor -= 1;
+            }
+            Direction::Right => {
+                self.cursor += 1;
+            }
+        }
+        self.current_state = rules.next_state;
+    }
+
+    fn run(&mut self) {
+        while self.step_count < self.step_limit {
+            self.step();
+            self.step_count += 1;
+        }
+    }
+
+    fn diagnostic_checksum(&self) -> usize {
+        self.tape.len()
+    }
+}
+
+pub struct Puzzle {
+    machine: Machine,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let mut machine = Machine::new();
+        machine.parse(input.lines().collect())?;
+
+        Ok(Puzzle { machine })
+    }
+
+    fn part1(&self) -> Answer {
+        let mut machine = self.machine.clone();
+        machine.run();
+        Ok(Some(machine.diagnostic_checksum().to_string()))
+    }
+
+    // Day 25 has no second puzzle
+    fn part2(&self) -> Answer {
+        Ok(None)
+    }
+}
@@ -0,0 +1,211 @@
+//
+// Knot hash, first described in day 10 and reused in day 14.
+//
+// A KnotHash holds a circular list of marks which is twisted by a
+// sequence of lengths. Calling twist directly gives the sparse hash
+// used by day 10 part 1. Feeding bytes through update and then
+// calling finalize gives the full 16-byte hash.
+//
+
+// Number of marks in the list for the full hash
+pub const SIZE: usize = 256;
+
+// Number of times the lengths are applied for the full hash
+pub const ROUNDS: usize = 64;
+
+// Standard length suffix appended to the input bytes
+pub const SUFFIX: [u8; 5] = [17, 31, 73, 47, 23];
+
+#[derive(Debug, Clone)]
+pub struct KnotHash {
+    list: Vec<u32>,
+    position: usize,
+    skip: usize,
+    input: Vec<u8>,
+    rounds: usize,
+    suffix: Vec<u8>,
+}
+
+impl Default for KnotHash {
+    fn default() -> KnotHash {
+        KnotHash::new(SIZE)
+    }
+}
+
+impl KnotHash {
+    pub fn new(size: usize) -> KnotHash {
+        KnotHash {
+            list: (0..size as u32).collect(),
+            position: 0,
+            skip: 0,
+            input: Vec::new(),
+            rounds: ROUNDS,
+            suffix: SUFFIX.to_vec(),
+        }
+    }
+
+    pub fn with_rounds(mut self, rounds: usize) -> KnotHash {
+        self.rounds = rounds;
+        self
+    }
+
+    pub fn with_suffix(mut self, suffix: &[u8]) -> KnotHash {
+        self.suffix = suffix.to_vec();
+        self
+    }
+
+    pub fn twist(&mut self, length: usize) {
+        let size = self.list.len();
+        assert!(length <= size, "length {} exceeds list size {}", length, size);
+
+        // - Reverse the order of that length of elements in the list,
+        // starting with the element at the current position.
+        let mut forward = self.position;
+        let mut backward = (self.position + length + size - 1) % size;
+        for _ in 0..(length / 2) {
+            self.list.swap(forward, backward);
+            forward += 1;
+            if forward == size {
+                forward = 0;
+            }
+            if backward == 0 {
+                backward = size - 1;
+            } else {
+                backward -= 1;
+            }
+        }
+
+        // - Move the current position forward by that length plus
+        // the skip size.
+        self.position = (self.position + length + self.skip) % size;
+
+        // - Increase the skip size by one.
+        self.skip += 1;
+    }
+
+    pub fn list(&self) -> &[u32] {
+        &self.list
+    }
+
+    pub fn product_first_two(&self) -> u32 {
+        self.list[0] * self.list[1]
+    }
+
+    // Input may arrive in any number of pieces; every round replays
+    // all of it, so it is kept until finalize.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.input.extend(bytes);
+    }
+
+    // Run the rounds over the input so far plus the suffix, starting
+    // from the current list, and reduce the resulting sparse hash.
+    // The list itself is left as it was, so more input may follow.
+    pub fn finalize(&self) -> [u8; 16] {
+        assert_eq!(SIZE, self.list.len(), "full hash needs {} marks", SIZE);
+
+        let mut kh = KnotHash {
+            input: Vec::new(),
+            ..self.clone()
+        };
+        for _ in 0..self.rounds {
+            for &length in self.input.iter().chain(self.suffix.iter()) {
+                kh.twist(length as usize);
+            }
+        }
+
+        dense_hash(&kh.list)
+    }
+
+    pub fn finalize_hex(&self) -> String {
+        to_hex(&self.finalize())
+    }
+}
+
+// Reduce a sparse hash of 256 marks to 16 bytes by XORing each block
+// of 16 marks together
+pub fn dense_hash(sparse_hash: &[u32]) -> [u8; 16] {
+    let mut result = [0; 16];
+
+    for (byte, block) in result.iter_mut().zip(sparse_hash.chunks(16)) {
+        *byte = block.iter().fold(0, |acc, &x| acc ^ x) as u8;
+    }
+
+    result
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// Full knot hash of input with the standard settings
+pub fn hash(input: &[u8]) -> [u8; 16] {
+    let mut kh = KnotHash::default();
+    kh.update(input);
+    kh.finalize()
+}
+
+pub fn hash_hex(input: &[u8]) -> String {
+    to_hex(&hash(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn twist_example() {
+        let mut kh = KnotHash::new(5);
+        for &length in &[3, 4, 1, 5] {
+            kh.twist(length);
+        }
+        assert_eq!(&[3, 4, 2, 1, 0], kh.list());
+        assert_eq!(12, kh.product_first_two());
+    }
+
+    #[test]
+    fn twist_zero_length() {
+        let mut kh = KnotHash::new(5);
+        kh.twist(0);
+        assert_eq!(&[0, 1, 2, 3, 4], kh.list());
+    }
+
+    #[test]
+    fn hash_provided_examples() {
+        assert_eq!("a2582a3a0e66e6e86e3812dcb672a272", hash_hex(b""));
+        assert_eq!("33efeb34ea91902bb2f59c9920caa6cd", hash_hex(b"AoC 2017"));
+        assert_eq!("3efbe78a8d82f29979031a4aa0b16a9d", hash_hex(b"1,2,3"));
+        assert_eq!("63960835bcdc130f0b66d7ff4f6a5a8e", hash_hex(b"1,2,4"));
+    }
+
+    #[test]
+    fn update_in_pieces() {
+        let mut kh = KnotHash::default();
+        kh.update(b"AoC");
+        kh.update(b" ");
+        kh.update(b"2017");
+        assert_eq!(hash(b"AoC 2017"), kh.finalize());
+    }
+
+    #[test]
+    fn configurable_rounds_and_suffix() {
+        // A single round with no suffix is the day 10 part 1 twist
+        let mut kh = KnotHash::default().with_rounds(1).with_suffix(&[]);
+        kh.update(&[3, 4, 1, 5]);
+
+        let mut twisted = KnotHash::default();
+        for &length in &[3, 4, 1, 5] {
+            twisted.twist(length);
+        }
+
+        assert_eq!(dense_hash(twisted.list()), kh.finalize());
+    }
+
+    #[test]
+    fn dense_hash_example() {
+        let mut sparse_hash = vec![0; 256];
+        sparse_hash[..16]
+            .copy_from_slice(&[65, 27, 9, 1, 4, 3, 40, 50, 91, 7, 6, 0, 2, 5, 68, 22]);
+        assert_eq!(64, dense_hash(&sparse_hash)[0]);
+        assert_eq!("4007ff", to_hex(&[64, 7, 255]));
+    }
+}
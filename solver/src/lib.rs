@@ -0,0 +1,326 @@
+//
+// Common interface implemented by each day's puzzle solution, so that
+// every day can be run the same way, either from its own binary or
+// from the aoc2017 runner.
+//
+extern crate pest;
+
+use std::error;
+use std::fmt::{self, Debug, Display, Error, Formatter};
+use std::io::{stdin, Read};
+use std::process;
+
+use pest::RuleType;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn all() -> Vec<Part> {
+        vec![Part::One, Part::Two]
+    }
+
+    pub fn from_number(number: &str) -> Option<Part> {
+        match number {
+            "1" => Some(Part::One),
+            "2" => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match *self {
+            Part::One => write!(f, "part 1"),
+            Part::Two => write!(f, "part 2"),
+        }
+    }
+}
+
+//
+// A problem found while parsing the puzzle input. Lines and columns
+// are numbered from 1, and text holds the whole offending line so
+// that the diagnostic can point at the column.
+//
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new<M: Display>(line: usize, column: usize, text: &str, message: M) -> ParseError {
+        ParseError {
+            line,
+            column,
+            text: text.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    // Report a problem with token, which must be a slice of text
+    // (e.g. as produced by split_whitespace), so that the column can
+    // be worked out from where token sits within text.
+    pub fn token<M: Display>(line: usize, text: &str, token: &str, message: M) -> ParseError {
+        let offset = token.as_ptr() as usize - text.as_ptr() as usize;
+        let column = text[..offset].chars().count() + 1;
+
+        ParseError::new(line, column, text, format!("{}: {:?}", message, token))
+    }
+
+    pub fn from_pest<R: RuleType>(error: pest::Error<R>) -> ParseError {
+        match error {
+            pest::Error::ParsingError {
+                positives,
+                negatives,
+                pos,
+            } => {
+                let message = match (positives.is_empty(), negatives.is_empty()) {
+                    (false, true) => format!("expected {}", list_rules(&positives)),
+                    (true, false) => format!("unexpected {}", list_rules(&negatives)),
+                    (false, false) => format!(
+                        "unexpected {}; expected {}",
+                        list_rules(&negatives),
+                        list_rules(&positives)
+                    ),
+                    (true, true) => "unexpected input".to_string(),
+                };
+                let (line, column) = pos.line_col();
+                ParseError::new(line, column, pos.line_of(), message)
+            }
+            pest::Error::CustomErrorPos { message, pos } => {
+                let (line, column) = pos.line_col();
+                ParseError::new(line, column, pos.line_of(), message)
+            }
+            pest::Error::CustomErrorSpan { message, span } => {
+                let pos = span.start_pos();
+                let (line, column) = pos.line_col();
+                ParseError::new(line, column, pos.line_of(), message)
+            }
+        }
+    }
+
+    // Parsers that handle one line at a time report line 1; this
+    // moves the error to the line's real place in the puzzle input.
+    pub fn at_line(self, line: usize) -> ParseError {
+        ParseError { line, ..self }
+    }
+}
+
+fn list_rules<R: Debug>(rules: &[R]) -> String {
+    let names: Vec<String> = rules.iter().map(|rule| format!("{:?}", rule)).collect();
+
+    match names.len() {
+        1 => names[0].clone(),
+        _ => format!("one of {}", names.join(", ")),
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let text = self.text.trim_end_matches(&['\r', '\n'][..]);
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        writeln!(f, "    {}", text)?;
+        write!(f, "    {:>width$}", "^", width = self.column)
+    }
+}
+
+impl error::Error for ParseError {}
+
+//
+// Parse each line of the input with parse_line, numbering any error
+// with the line on which it was found.
+//
+pub fn parse_lines<T, F>(input: &str, parse_line: F) -> Result<Vec<T>, ParseError>
+where
+    F: Fn(&str) -> Result<T, ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse_line(line).map_err(|e| e.at_line(index + 1)))
+        .collect()
+}
+
+//
+// A part which could not be answered, though the puzzle input parsed:
+// a program in the input which faults while running, for instance.
+//
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolveError {
+    pub message: String,
+}
+
+impl SolveError {
+    pub fn new<M: Display>(message: M) -> SolveError {
+        SolveError {
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl error::Error for SolveError {}
+
+// The answer to one part, which is None when the puzzle has no such
+// part (day 25 has no second puzzle)
+pub type Answer = Result<Option<String>, SolveError>;
+
+//
+// A solver parses the puzzle input once, then answers either part
+// from the parsed form.
+//
+pub trait Solver: Sized {
+    fn parse(input: &str) -> Result<Self, ParseError>;
+
+    fn part1(&self) -> Answer;
+
+    fn part2(&self) -> Answer;
+
+    fn solve(&self, part: Part) -> Answer {
+        match part {
+            Part::One => self.part1(),
+            Part::Two => self.part2(),
+        }
+    }
+}
+
+// Answer each of parts in turn, stopping at the first which fails
+pub fn run<S: Solver>(input: &str, parts: &[Part]) -> Result<Vec<(Part, Answer)>, ParseError> {
+    let solver = S::parse(input)?;
+
+    let mut results = Vec::new();
+    for &part in parts {
+        let answer = solver.solve(part);
+        let failed = answer.is_err();
+        results.push((part, answer));
+        if failed {
+            break;
+        }
+    }
+    Ok(results)
+}
+
+// Print the answers, returning the part which failed, if one did
+pub fn report(results: &[(Part, Answer)]) -> Result<(), (Part, SolveError)> {
+    for &(part, ref answer) in results {
+        match *answer {
+            Ok(Some(ref answer)) => println!("{}: {}", part, answer),
+            Ok(None) => println!("{}: no answer", part),
+            Err(ref e) => return Err((part, e.clone())),
+        }
+    }
+    Ok(())
+}
+
+//
+// Entry point shared by each day's own binary: read the puzzle input
+// from stdin and report both parts, or explain why the input could
+// not be parsed or a part could not be answered.
+//
+pub fn main<S: Solver>() {
+    let mut input = String::new();
+
+    if let Err(e) = stdin().read_to_string(&mut input) {
+        eprintln!("error: could not read puzzle input: {}", e);
+        process::exit(1);
+    }
+
+    match run::<S>(&input, &Part::all()) {
+        Ok(results) => {
+            if let Err((part, e)) = report(&results) {
+                eprintln!("error: {} failed: {}", part, e);
+                process::exit(1);
+            }
+        }
+        Err(e) => {
+            eprintln!("error: invalid puzzle input at {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_column() {
+        let line = "5 1 x 5";
+        let token = line.split_whitespace().nth(2).unwrap();
+        let e = ParseError::token(1, line, token, "invalid number");
+        assert_eq!(5, e.column);
+        assert_eq!("invalid number: \"x\"", e.message);
+    }
+
+    #[test]
+    fn parse_lines_numbers_errors() {
+        let e = parse_lines("1\n2\nthree\n", |line| {
+            line.parse::<u32>()
+                .map_err(|e| ParseError::token(1, line, line, e))
+        }).unwrap_err();
+        assert_eq!((3, 1), (e.line, e.column));
+        assert_eq!("three", e.text);
+    }
+
+    #[test]
+    fn display_points_at_column() {
+        let e = ParseError::new(4, 3, "ab?d", "unexpected character");
+        assert_eq!(
+            "line 4, column 3: unexpected character\n    ab?d\n      ^",
+            e.to_string()
+        );
+    }
+
+    // Fails part 1 unless the input is "ok"
+    struct Failing {
+        ok: bool,
+    }
+
+    impl Solver for Failing {
+        fn parse(input: &str) -> Result<Failing, ParseError> {
+            Ok(Failing { ok: input == "ok" })
+        }
+
+        fn part1(&self) -> Answer {
+            if self.ok {
+                Ok(Some("1".to_string()))
+            } else {
+                Err(SolveError::new("part 1 failed"))
+            }
+        }
+
+        fn part2(&self) -> Answer {
+            Ok(None)
+        }
+    }
+
+    #[test]
+    fn run_stops_at_failure() {
+        let results = run::<Failing>("ok", &Part::all()).unwrap();
+        assert_eq!(2, results.len());
+        assert_eq!(Ok(()), report(&results));
+
+        let results = run::<Failing>("", &Part::all()).unwrap();
+        assert_eq!(1, results.len());
+        assert_eq!(
+            Err((Part::One, SolveError::new("part 1 failed"))),
+            report(&results)
+        );
+    }
+}
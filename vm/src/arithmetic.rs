@@ -0,0 +1,49 @@
+use error::Fault;
+
+//
+// What add, sub and mul do when the result does not fit in an
+// i64: stop with an error, wrap around, or clamp to the nearest bound.
+// A zero divisor is an error whatever the policy.
+//
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum Arithmetic {
+    #[default]
+    Checked,
+    Wrapping,
+    Saturating,
+}
+
+impl Arithmetic {
+    pub fn add(self, x: i64, y: i64) -> Result<i64, Fault> {
+        match self {
+            Arithmetic::Checked => x.checked_add(y).ok_or(Fault::Overflow),
+            Arithmetic::Wrapping => Ok(x.wrapping_add(y)),
+            Arithmetic::Saturating => Ok(x.saturating_add(y)),
+        }
+    }
+
+    pub fn sub(self, x: i64, y: i64) -> Result<i64, Fault> {
+        match self {
+            Arithmetic::Checked => x.checked_sub(y).ok_or(Fault::Overflow),
+            Arithmetic::Wrapping => Ok(x.wrapping_sub(y)),
+            Arithmetic::Saturating => Ok(x.saturating_sub(y)),
+        }
+    }
+
+    pub fn mul(self, x: i64, y: i64) -> Result<i64, Fault> {
+        match self {
+            Arithmetic::Checked => x.checked_mul(y).ok_or(Fault::Overflow),
+            Arithmetic::Wrapping => Ok(x.wrapping_mul(y)),
+            Arithmetic::Saturating => Ok(x.saturating_mul(y)),
+        }
+    }
+
+    // A remainder always fits: wrapping_rem gives i64::MIN % -1 its
+    // true value of 0, where checked_rem would report an overflow
+    pub fn rem(self, x: i64, y: i64) -> Result<i64, Fault> {
+        if y == 0 {
+            return Err(Fault::DivideByZero);
+        }
+        Ok(x.wrapping_rem(y))
+    }
+}
@@ -0,0 +1,86 @@
+//
+// The machine does not run the parsed instructions directly. They are
+// first lowered into bytecode, in which each register is replaced by
+// its slot in an array of registers and each jump by a constant offset
+// is replaced by the index it lands on.
+//
+use instruction::{Instruction, Operand};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Value {
+    Slot(usize),
+    Imm(i64),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Jump {
+    // The absolute index of the target
+    To(i64),
+    // A constant offset taking the target beyond the range of an i64
+    Overflow,
+    // An offset held in a register, only known when the jump is taken
+    By(usize),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Op {
+    Snd(Value),
+    Set(usize, Value),
+    Add(usize, Value),
+    Sub(usize, Value),
+    Mul(usize, Value),
+    Mod(usize, Value),
+    Rcv(Value),
+    Jgz(Value, Jump),
+    Jnz(Value, Jump),
+}
+
+// The slot holding register, giving it a new one if it has none yet
+pub fn slot(names: &mut Vec<char>, register: char) -> usize {
+    match names.iter().position(|&name| name == register) {
+        Some(slot) => slot,
+        None => {
+            names.push(register);
+            names.len() - 1
+        }
+    }
+}
+
+fn value(names: &mut Vec<char>, operand: Operand) -> Value {
+    match operand {
+        Operand::Reg(register) => Value::Slot(slot(names, register)),
+        Operand::Imm(number) => Value::Imm(number),
+    }
+}
+
+fn jump(names: &mut Vec<char>, index: usize, operand: Operand) -> Jump {
+    match operand {
+        Operand::Reg(register) => Jump::By(slot(names, register)),
+        Operand::Imm(offset) => match (index as i64).checked_add(offset) {
+            Some(target) => Jump::To(target),
+            None => Jump::Overflow,
+        },
+    }
+}
+
+//
+// Lower the instructions into bytecode. The slot of each register is
+// its position in names, to which any register not already there is
+// added.
+//
+pub fn compile(code: &[Instruction], names: &mut Vec<char>) -> Vec<Op> {
+    code.iter()
+        .enumerate()
+        .map(|(index, instruction)| match *instruction {
+            Instruction::Snd(x) => Op::Snd(value(names, x)),
+            Instruction::Set(register, y) => Op::Set(slot(names, register), value(names, y)),
+            Instruction::Add(register, y) => Op::Add(slot(names, register), value(names, y)),
+            Instruction::Sub(register, y) => Op::Sub(slot(names, register), value(names, y)),
+            Instruction::Mul(register, y) => Op::Mul(slot(names, register), value(names, y)),
+            Instruction::Mod(register, y) => Op::Mod(slot(names, register), value(names, y)),
+            Instruction::Rcv(x) => Op::Rcv(value(names, x)),
+            Instruction::Jgz(x, y) => Op::Jgz(value(names, x), jump(names, index, y)),
+            Instruction::Jnz(x, y) => Op::Jnz(value(names, x), jump(names, index, y)),
+        })
+        .collect()
+}
@@ -0,0 +1,232 @@
+//
+// Interactive debugger for programs run on the register machine.
+// Commands are read one per line from any BufRead, normally stdin,
+// and responses written to any Write, so that a session can equally
+// be scripted in a test:
+//
+//     break N     stop when about to execute instruction N
+//     delete N    remove the breakpoint at instruction N
+//     step [N]    execute one (or N) instructions, showing each
+//     continue    run until a breakpoint, a stop, a halt or an error
+//     registers   show every register
+//     print X     show register X
+//     trace       toggle showing each instruction while continuing
+//     quit        leave the debugger
+//
+use error::VmError;
+use instruction::Instruction;
+use machine::{Machine, Status};
+use io::Io;
+use trace::{TraceEvent, Tracer};
+
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::io::{self, BufRead, Write};
+use std::rc::Rc;
+
+// Whatever the debugger can drive, such as a bare machine or a
+// puzzle's wrapper around one
+pub trait Debuggee {
+    fn step(&mut self) -> Result<Status, VmError>;
+
+    fn instruction_pointer(&self) -> i64;
+
+    fn current_instruction(&self) -> Option<Instruction>;
+
+    fn registers(&self) -> Vec<(char, i64)>;
+
+    fn set_tracer(&mut self, tracer: Option<Box<dyn Tracer>>);
+}
+
+impl<I: Io> Debuggee for Machine<I> {
+    fn step(&mut self) -> Result<Status, VmError> {
+        Machine::step(self)
+    }
+
+    fn instruction_pointer(&self) -> i64 {
+        Machine::instruction_pointer(self)
+    }
+
+    fn current_instruction(&self) -> Option<Instruction> {
+        Machine::current_instruction(self).cloned()
+    }
+
+    fn registers(&self) -> Vec<(char, i64)> {
+        Machine::registers(self)
+    }
+
+    fn set_tracer(&mut self, tracer: Option<Box<dyn Tracer>>) {
+        Machine::set_tracer(self, tracer)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Debugger {
+    breakpoints: BTreeSet<i64>,
+    tracing: bool,
+}
+
+impl Debugger {
+    pub fn new() -> Debugger {
+        Debugger::default()
+    }
+
+    fn show_location<D: Debuggee, W: Write>(debuggee: &D, output: &mut W) -> io::Result<()> {
+        match debuggee.current_instruction() {
+            Some(instruction) => writeln!(
+                output,
+                "{:>4}: {}",
+                debuggee.instruction_pointer(),
+                instruction
+            ),
+            None => writeln!(output, "halted at {}", debuggee.instruction_pointer()),
+        }
+    }
+
+    fn show_events<W: Write>(events: &RefCell<Vec<TraceEvent>>, output: &mut W) -> io::Result<()> {
+        for event in events.borrow_mut().drain(..) {
+            writeln!(output, "{}", event)?;
+        }
+        Ok(())
+    }
+
+    fn show_status<W: Write>(status: Status, output: &mut W) -> io::Result<()> {
+        match status {
+            Status::Running => Ok(()),
+            Status::Stopped => writeln!(output, "stopped at rcv"),
+            Status::Halted => writeln!(output, "halted"),
+        }
+    }
+
+    // Step once, reporting any error; the debuggee is left on the
+    // instruction which failed, so the error stops execution
+    fn step<D: Debuggee, W: Write>(debuggee: &mut D, output: &mut W) -> io::Result<Option<Status>> {
+        match debuggee.step() {
+            Ok(status) => Ok(Some(status)),
+            Err(e) => {
+                writeln!(output, "error: {}", e)?;
+                Ok(None)
+            }
+        }
+    }
+
+    //
+    // Run the session until quit or the end of input. Returns the
+    // status of the last instruction executed.
+    //
+    pub fn run<D, R, W>(&mut self, debuggee: &mut D, input: R, mut output: W) -> io::Result<Status>
+    where
+        D: Debuggee,
+        R: BufRead,
+        W: Write,
+    {
+        let events: Rc<RefCell<Vec<TraceEvent>>> = Rc::new(RefCell::new(Vec::new()));
+        let sink = events.clone();
+        debuggee.set_tracer(Some(Box::new(move |event: &TraceEvent| {
+            sink.borrow_mut().push(event.clone())
+        })));
+
+        let mut status = Status::Running;
+        Debugger::show_location(debuggee, &mut output)?;
+
+        let mut lines = input.lines();
+        loop {
+            write!(output, "(debug) ")?;
+            output.flush()?;
+
+            let line = match lines.next() {
+                Some(line) => line?,
+                None => break,
+            };
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let number = words.get(1).and_then(|word| word.parse::<i64>().ok());
+
+            match (words.first().cloned(), number) {
+                (None, _) => {}
+                (Some("b"), Some(n)) | (Some("break"), Some(n)) => {
+                    self.breakpoints.insert(n);
+                    writeln!(output, "breakpoint at {}", n)?;
+                }
+                (Some("d"), Some(n)) | (Some("delete"), Some(n)) => {
+                    if self.breakpoints.remove(&n) {
+                        writeln!(output, "deleted breakpoint at {}", n)?;
+                    } else {
+                        writeln!(output, "no breakpoint at {}", n)?;
+                    }
+                }
+                (Some("s"), n) | (Some("step"), n) => {
+                    for _ in 0..n.unwrap_or(1).max(1) {
+                        status = match Debugger::step(debuggee, &mut output)? {
+                            Some(status) => status,
+                            None => break,
+                        };
+                        Debugger::show_events(&events, &mut output)?;
+                        if status != Status::Running {
+                            break;
+                        }
+                    }
+                    Debugger::show_status(status, &mut output)?;
+                    Debugger::show_location(debuggee, &mut output)?;
+                }
+                (Some("c"), _) | (Some("continue"), _) => {
+                    loop {
+                        status = match Debugger::step(debuggee, &mut output)? {
+                            Some(status) => status,
+                            None => break,
+                        };
+                        if self.tracing {
+                            Debugger::show_events(&events, &mut output)?;
+                        } else {
+                            events.borrow_mut().clear();
+                        }
+                        if status != Status::Running {
+                            break;
+                        }
+                        let ip = debuggee.instruction_pointer();
+                        if self.breakpoints.contains(&ip) {
+                            writeln!(output, "breakpoint at {}", ip)?;
+                            break;
+                        }
+                    }
+                    Debugger::show_status(status, &mut output)?;
+                    Debugger::show_location(debuggee, &mut output)?;
+                }
+                (Some("r"), _) | (Some("registers"), _) => {
+                    let registers: Vec<String> = debuggee
+                        .registers()
+                        .iter()
+                        .map(|&(register, value)| format!("{} = {}", register, value))
+                        .collect();
+                    writeln!(output, "{}", registers.join(", "))?;
+                }
+                (Some("p"), _) | (Some("print"), _) => {
+                    let register = words.get(1).and_then(|word| word.chars().next());
+                    let value = debuggee
+                        .registers()
+                        .into_iter()
+                        .find(|&(r, _)| Some(r) == register);
+                    match value {
+                        Some((register, value)) => writeln!(output, "{} = {}", register, value)?,
+                        None => writeln!(output, "no such register")?,
+                    }
+                }
+                (Some("t"), _) | (Some("trace"), _) => {
+                    self.tracing = !self.tracing;
+                    writeln!(output, "tracing {}", if self.tracing { "on" } else { "off" })?;
+                }
+                (Some("q"), _) | (Some("quit"), _) => break,
+                (Some(command), _) => {
+                    writeln!(output, "unknown command: {}", command)?;
+                    writeln!(
+                        output,
+                        "commands: break N, delete N, step [N], continue, registers, print X, trace, quit"
+                    )?;
+                }
+            }
+        }
+
+        debuggee.set_tracer(None);
+
+        Ok(status)
+    }
+}
@@ -0,0 +1,63 @@
+use flow::{basic_blocks, control_flow, jump_target, Target};
+use instruction::{Instruction, Operand};
+
+use std::collections::BTreeSet;
+
+fn has_jump_offset(instruction: &Instruction) -> bool {
+    matches!(
+        *instruction,
+        Instruction::Jgz(_, Operand::Imm(_)) | Instruction::Jnz(_, Operand::Imm(_))
+    )
+}
+
+fn describe(target: Target) -> String {
+    match target {
+        Target::Block(block) => format!("block {}", block),
+        Target::Exit => "exit".to_string(),
+        Target::Unknown => "?".to_string(),
+    }
+}
+
+//
+// A listing of the code segment, one instruction per line as the
+// parser reads it, with its index. Every jump target gets a label,
+// named after its index, which the jumps to it mention in a comment.
+// Each basic block is headed by a comment giving its successors.
+//
+//     ; block 1 -> block 1, block 2
+//     L1:    1  sub a 1
+//            2  jgz a -1  ; L1
+//
+pub fn disassemble(code: &[Instruction]) -> String {
+    let labels: BTreeSet<usize> = (0..code.len())
+        .filter_map(|index| jump_target(code, index))
+        .collect();
+
+    let edges = control_flow(code);
+    let mut listing = String::new();
+    for (number, block) in basic_blocks(code).into_iter().enumerate() {
+        let successors: Vec<String> = edges
+            .iter()
+            .filter(|edge| edge.from == number)
+            .map(|edge| describe(edge.to))
+            .collect();
+        listing += &format!("; block {} -> {}\n", number, successors.join(", "));
+
+        for index in block {
+            let label = if labels.contains(&index) {
+                format!("L{}:", index)
+            } else {
+                String::new()
+            };
+            listing += &format!("{:<6}{:>4}  {}", label, index, code[index]);
+            if has_jump_offset(&code[index]) {
+                match jump_target(code, index) {
+                    Some(target) => listing += &format!("  ; L{}", target),
+                    None => listing += "  ; exit",
+                }
+            }
+            listing += "\n";
+        }
+    }
+    listing
+}
@@ -0,0 +1,50 @@
+use instruction::Instruction;
+
+use std::error;
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Fault {
+    // The result does not fit in an i64, under checked arithmetic
+    Overflow,
+    // mod by zero
+    DivideByZero,
+    // A jump to an index beyond the range of an i64
+    JumpOutOfBounds,
+    // A register the machine does not have
+    UnknownRegister(char),
+}
+
+impl Display for Fault {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Fault::Overflow => write!(f, "arithmetic overflow"),
+            Fault::DivideByZero => write!(f, "division by zero"),
+            Fault::JumpOutOfBounds => write!(f, "jump out of bounds beyond the range of i64"),
+            Fault::UnknownRegister(register) => write!(f, "unknown register {}", register),
+        }
+    }
+}
+
+//
+// An instruction the machine could not execute. The instruction
+// pointer is left on it, and no register is changed.
+//
+#[derive(Debug, Clone, PartialEq)]
+pub struct VmError {
+    pub instruction_pointer: i64,
+    pub instruction: Instruction,
+    pub fault: Fault,
+}
+
+impl Display for VmError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} at instruction {} ({})",
+            self.fault, self.instruction_pointer, self.instruction
+        )
+    }
+}
+
+impl error::Error for VmError {}
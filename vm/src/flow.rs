@@ -0,0 +1,126 @@
+use instruction::{Instruction, Operand};
+
+use std::ops::Range;
+
+// Where a jump at index may go, if its offset is known before running
+pub fn jump_target(code: &[Instruction], index: usize) -> Option<usize> {
+    let offset = match code[index] {
+        Instruction::Jgz(_, Operand::Imm(offset)) | Instruction::Jnz(_, Operand::Imm(offset)) => {
+            offset
+        }
+        _ => return None,
+    };
+    let target = (index as i64).checked_add(offset)?;
+    if target < 0 || target >= code.len() as i64 {
+        return None;
+    }
+    Some(target as usize)
+}
+
+fn is_jump(instruction: &Instruction) -> bool {
+    matches!(*instruction, Instruction::Jgz(_, _) | Instruction::Jnz(_, _))
+}
+
+//
+// Split the code segment into basic blocks: runs of instructions which
+// are only entered at the top and only left at the bottom. A block
+// starts at the beginning of the program, at every jump target, and
+// after every jump. A jump by a register could land anywhere, so its
+// target is not known; the blocks are only exact for immediate offsets.
+//
+pub fn basic_blocks(code: &[Instruction]) -> Vec<Range<usize>> {
+    let mut leaders = vec![false; code.len() + 1];
+    leaders[0] = true;
+    leaders[code.len()] = true;
+    for (index, instruction) in code.iter().enumerate() {
+        if is_jump(instruction) {
+            leaders[index + 1] = true;
+        }
+        if let Some(target) = jump_target(code, index) {
+            leaders[target] = true;
+        }
+    }
+
+    let mut blocks = Vec::new();
+    let mut start = 0;
+    for (index, &leader) in leaders.iter().enumerate().skip(1) {
+        if leader {
+            blocks.push(start..index);
+            start = index;
+        }
+    }
+    blocks
+}
+
+// Where control may go on leaving a basic block
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Target {
+    // The index of a block, as returned by basic_blocks
+    Block(usize),
+    // Off either end of the program
+    Exit,
+    // A jump by a register
+    Unknown,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Edge {
+    pub from: usize,
+    pub to: Target,
+}
+
+// Whether a jump is taken, when its condition is a number
+fn always_jumps(instruction: &Instruction) -> Option<bool> {
+    match *instruction {
+        Instruction::Jgz(Operand::Imm(x), _) => Some(x > 0),
+        Instruction::Jnz(Operand::Imm(x), _) => Some(x != 0),
+        _ => None,
+    }
+}
+
+//
+// The control flow graph between the basic blocks of the code segment.
+// A block ending in a jump has an edge to the jump target, unless the
+// jump is never taken, and an edge to the next block, unless it always
+// is. Any other block falls through to the next.
+//
+pub fn control_flow(code: &[Instruction]) -> Vec<Edge> {
+    let blocks = basic_blocks(code);
+    let block_of = |index: Option<i64>| {
+        let index = match index {
+            Some(index) if index >= 0 && index < code.len() as i64 => index,
+            _ => return Target::Exit,
+        };
+        let position = blocks
+            .iter()
+            .position(|block| block.contains(&(index as usize)))
+            .unwrap();
+        Target::Block(position)
+    };
+
+    let mut edges = Vec::new();
+    for (from, block) in blocks.iter().enumerate() {
+        let last = block.end - 1;
+        let instruction = &code[last];
+        let mut targets = Vec::new();
+        if is_jump(instruction) {
+            if always_jumps(instruction) != Some(false) {
+                targets.push(match *instruction {
+                    Instruction::Jgz(_, Operand::Imm(offset))
+                    | Instruction::Jnz(_, Operand::Imm(offset)) => {
+                        block_of((last as i64).checked_add(offset))
+                    }
+                    _ => Target::Unknown,
+                });
+            }
+            if always_jumps(instruction) != Some(true) {
+                targets.push(block_of(Some(block.end as i64)));
+            }
+        } else {
+            targets.push(block_of(Some(block.end as i64)));
+        }
+        targets.dedup();
+        edges.extend(targets.into_iter().map(|to| Edge { from, to }));
+    }
+    edges
+}
@@ -0,0 +1,75 @@
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Operand {
+    Reg(char),
+    Imm(i64),
+}
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Operand::Reg(register) => write!(f, "{}", register),
+            Operand::Imm(number) => write!(f, "{}", number),
+        }
+    }
+}
+
+//
+// The instruction set shared by the day 18 duet and the day 23
+// coprocessor. The first operand of set, add, sub, mul and mod is
+// always the register being written.
+//
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Instruction {
+    Snd(Operand),
+    Set(char, Operand),
+    Add(char, Operand),
+    Sub(char, Operand),
+    Mul(char, Operand),
+    Mod(char, Operand),
+    Rcv(Operand),
+    Jgz(Operand, Operand),
+    Jnz(Operand, Operand),
+}
+
+impl Instruction {
+    // The name of the instruction, as written in the assembly language
+    pub fn mnemonic(&self) -> &'static str {
+        match *self {
+            Instruction::Snd(_) => "snd",
+            Instruction::Set(_, _) => "set",
+            Instruction::Add(_, _) => "add",
+            Instruction::Sub(_, _) => "sub",
+            Instruction::Mul(_, _) => "mul",
+            Instruction::Mod(_, _) => "mod",
+            Instruction::Rcv(_) => "rcv",
+            Instruction::Jgz(_, _) => "jgz",
+            Instruction::Jnz(_, _) => "jnz",
+        }
+    }
+
+    // Every operand, written or read, in the order they appear
+    pub fn operands(&self) -> Vec<Operand> {
+        match *self {
+            Instruction::Snd(x) | Instruction::Rcv(x) => vec![x],
+            Instruction::Set(register, y)
+            | Instruction::Add(register, y)
+            | Instruction::Sub(register, y)
+            | Instruction::Mul(register, y)
+            | Instruction::Mod(register, y) => vec![Operand::Reg(register), y],
+            Instruction::Jgz(x, y) | Instruction::Jnz(x, y) => vec![x, y],
+        }
+    }
+}
+
+// Written back out as assembly, in the form the parser reads
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.mnemonic())?;
+        for operand in self.operands() {
+            write!(f, " {}", operand)?;
+        }
+        Ok(())
+    }
+}
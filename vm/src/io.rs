@@ -0,0 +1,128 @@
+//
+// What snd and rcv mean depends on the puzzle: in day 18 part 1 they
+// play and recover sounds, in day 18 part 2 they pass messages between
+// programs, and the day 23 coprocessor has no use for them at all.
+//
+use std::collections::VecDeque;
+
+// What a rcv instruction should do with the value of its operand
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Received {
+    // Store the value in the operand's register
+    Value(i64),
+    // Carry on with the next instruction
+    Ignored,
+    // Stop execution, leaving the instruction pointer at the rcv
+    Stop,
+}
+
+pub trait Io {
+    fn snd(&mut self, value: i64);
+
+    fn rcv(&mut self, value: i64) -> Received;
+}
+
+//
+// snd plays a sound with the given frequency; rcv recovers the last
+// frequency played, unless its operand is zero, and stops execution.
+//
+#[derive(Debug, Default)]
+pub struct Sound {
+    frequency: i64,
+    recovered: Option<i64>,
+}
+
+impl Sound {
+    pub fn new() -> Sound {
+        Sound::default()
+    }
+
+    pub fn recovered(&self) -> Option<i64> {
+        self.recovered
+    }
+}
+
+impl Io for Sound {
+    fn snd(&mut self, value: i64) {
+        self.frequency = value;
+    }
+
+    fn rcv(&mut self, value: i64) -> Received {
+        if value == 0 {
+            return Received::Ignored;
+        }
+        self.recovered = Some(self.frequency);
+        Received::Stop
+    }
+}
+
+//
+// snd sends a value to another program; rcv takes the oldest value
+// received from another program and stores it in its register, or
+// stops execution when there is none, so that the other program can
+// be given a turn. Delivering values between programs is left to
+// whoever runs them.
+//
+#[derive(Debug, Default)]
+pub struct Messages {
+    inbox: VecDeque<i64>,
+    outbox: Vec<i64>,
+    send_counter: u32,
+    receive_counter: u32,
+}
+
+impl Messages {
+    pub fn new() -> Messages {
+        Messages::default()
+    }
+
+    pub fn deliver(&mut self, value: i64) {
+        self.inbox.push_back(value);
+    }
+
+    // Values sent since the last call, oldest first
+    pub fn take_sent(&mut self) -> Vec<i64> {
+        self.outbox.split_off(0)
+    }
+
+    pub fn has_pending(&self) -> bool {
+        !self.inbox.is_empty()
+    }
+
+    pub fn send_counter(&self) -> u32 {
+        self.send_counter
+    }
+
+    pub fn receive_counter(&self) -> u32 {
+        self.receive_counter
+    }
+}
+
+impl Io for Messages {
+    fn snd(&mut self, value: i64) {
+        self.outbox.push(value);
+        self.send_counter += 1;
+    }
+
+    fn rcv(&mut self, _value: i64) -> Received {
+        match self.inbox.pop_front() {
+            Some(received) => {
+                self.receive_counter += 1;
+                Received::Value(received)
+            }
+            None => Received::Stop,
+        }
+    }
+}
+
+// snd and rcv do nothing
+#[derive(Debug, Default)]
+pub struct Silent;
+
+impl Io for Silent {
+    fn snd(&mut self, _value: i64) {}
+
+    fn rcv(&mut self, _value: i64) -> Received {
+        Received::Ignored
+    }
+}
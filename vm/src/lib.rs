@@ -0,0 +1,307 @@
+//
+// Register machine shared by the day 18 duet and the day 23
+// coprocessor. Both run the same assembly language; they differ only
+// in what snd and rcv do, which is supplied through the Io trait.
+//
+extern crate pest;
+#[macro_use]
+extern crate pest_derive;
+extern crate solver;
+
+mod arithmetic;
+mod bytecode;
+mod debugger;
+mod disassembly;
+mod error;
+mod flow;
+mod instruction;
+mod io;
+mod machine;
+mod parsing;
+mod trace;
+mod validate;
+
+pub use arithmetic::Arithmetic;
+pub use debugger::{Debuggee, Debugger};
+pub use disassembly::disassemble;
+pub use error::{Fault, VmError};
+pub use flow::{basic_blocks, control_flow, Edge, Target};
+pub use instruction::{Instruction, Operand};
+pub use io::{Io, Messages, Received, Silent, Sound};
+pub use machine::{Machine, Status};
+pub use parsing::parse_line;
+pub use trace::{Change, TraceEvent, Tracer};
+pub use validate::{validate, Unsupported};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(lines: &[&str]) -> Vec<Instruction> {
+        lines.iter().map(|line| parse_line(line).unwrap()).collect()
+    }
+
+    #[test]
+    fn parse_operand_forms() {
+        assert_eq!(
+            vec![
+                Instruction::Snd(Operand::Imm(-4)),
+                Instruction::Set('a', Operand::Reg('b')),
+                Instruction::Mod('a', Operand::Imm(5)),
+                Instruction::Rcv(Operand::Reg('c')),
+                Instruction::Jgz(Operand::Imm(1), Operand::Reg('p')),
+                Instruction::Jnz(Operand::Reg('g'), Operand::Imm(-8)),
+            ],
+            parse(&["snd -4", "set a b", "mod a 5", "rcv c", "jgz 1 p", "jnz g -8"])
+        );
+    }
+
+    #[test]
+    fn parse_rejects_number_target() {
+        let e = parse_line("set 1 a").unwrap_err();
+        assert_eq!(5, e.column);
+    }
+
+    #[test]
+    fn parse_rejects_trailing_text() {
+        assert!(parse_line("set a 1 junk").is_err());
+        assert!(parse_line("set a 12x").is_err());
+        assert!(parse_line("jgz a -1 ").is_err());
+    }
+
+    #[test]
+    fn sound_provided_example() {
+        let instructions = parse(&[
+            "set a 1", "add a 2", "mul a a", "mod a 5", "snd a", "set a 0", "rcv a",
+            "jgz a -1", "set a 1", "jgz a -2",
+        ]);
+        let mut machine = Machine::new(instructions, Sound::new());
+        assert_eq!(Ok(Status::Stopped), machine.run());
+        assert_eq!(Some(4), machine.io().recovered());
+    }
+
+    #[test]
+    fn silent_runs_off_the_end() {
+        let instructions = parse(&["set b 3", "sub b 1", "mul c 2", "jnz b -2", "rcv b"]);
+        let mut machine = Machine::new(instructions, Silent);
+        machine.set_register('c', 1);
+        assert_eq!(Ok(Status::Halted), machine.run());
+        assert_eq!(8, machine.register('c'));
+        assert_eq!(5, machine.instruction_pointer());
+    }
+
+    #[test]
+    fn tracer_sees_register_changes() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let instructions = parse(&["set a 3", "snd a", "mul a a"]);
+        let mut machine = Machine::new(instructions, Sound::new());
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let sink = events.clone();
+        machine.set_tracer(Some(Box::new(move |event: &TraceEvent| {
+            sink.borrow_mut().push(event.clone())
+        })));
+        machine.run().unwrap();
+
+        let events = events.borrow();
+        assert_eq!(3, events.len());
+        assert_eq!(
+            vec![Change { register: 'a', old: 3, new: 9 }],
+            events[2].changes
+        );
+        assert!(events[1].changes.is_empty());
+        assert_eq!(2, events[2].instruction_pointer);
+    }
+
+    #[test]
+    fn basic_blocks_split_at_jumps() {
+        let instructions = parse(&["set a 3", "sub a 1", "jgz a -1", "snd a", "jnz 1 -4", "rcv a"]);
+        assert_eq!(vec![0..1, 1..3, 3..5, 5..6], basic_blocks(&instructions));
+        assert!(basic_blocks(&[]).is_empty());
+    }
+
+    #[test]
+    fn control_flow_edges() {
+        let instructions = parse(&["set a 3", "sub a 1", "jgz a -1", "snd a", "jnz 1 -4", "rcv a"]);
+        let edge = |from, to| Edge { from, to };
+        assert_eq!(
+            vec![
+                edge(0, Target::Block(1)),
+                edge(1, Target::Block(1)),
+                edge(1, Target::Block(2)),
+                edge(2, Target::Block(0)),
+                edge(3, Target::Exit),
+            ],
+            control_flow(&instructions)
+        );
+        let instructions = parse(&["jgz a b", "jgz 0 5"]);
+        assert_eq!(
+            vec![
+                edge(0, Target::Unknown),
+                edge(0, Target::Block(1)),
+                edge(1, Target::Exit),
+            ],
+            control_flow(&instructions)
+        );
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let lines = [
+            "snd a", "snd -4", "set a b", "set a 12", "add a -1", "sub a b", "mul a 2",
+            "mod a b", "rcv a", "rcv 0", "jgz a -2", "jgz 1 b", "jnz 1 3", "jnz a b",
+        ];
+        for line in lines.iter() {
+            let instruction = parse_line(line).unwrap();
+            assert_eq!(*line, instruction.to_string());
+            assert_eq!(instruction, parse_line(&instruction.to_string()).unwrap());
+        }
+    }
+
+    #[test]
+    fn disassemble_labels_jumps() {
+        let instructions = parse(&["set a 3", "sub a 1", "jgz a -1", "jnz 1 2", "snd a"]);
+        assert_eq!(
+            "; block 0 -> block 1
+         0  set a 3
+; block 1 -> block 1, block 2
+L1:      1  sub a 1
+         2  jgz a -1  ; L1
+; block 2 -> exit
+         3  jnz 1 2  ; exit
+; block 3 -> exit
+         4  snd a
+",
+            disassemble(&instructions)
+        );
+    }
+
+    #[test]
+    fn jump_offset_overflow() {
+        let instructions = parse(&["snd a", "jgz 1 9223372036854775807"]);
+        assert_eq!(
+            "; block 0 -> exit
+         0  snd a
+         1  jgz 1 9223372036854775807  ; exit
+",
+            disassemble(&instructions)
+        );
+
+        let mut machine = Machine::new(instructions, Sound::new());
+        assert_eq!(Fault::JumpOutOfBounds, machine.run().unwrap_err().fault);
+    }
+
+    #[test]
+    fn registers_outside_the_program() {
+        let instructions = parse(&["add a 2", "jgz a b", "set a 7", "snd a"]);
+        let mut machine = Machine::new(instructions, Sound::new());
+        machine.set_register('z', 4);
+        machine.set_register('b', 2);
+        machine.run().unwrap();
+        assert_eq!(vec![('a', 2), ('b', 2), ('z', 4)], machine.registers());
+        assert_eq!(0, machine.register('q'));
+    }
+
+    #[test]
+    fn arithmetic_policies() {
+        let instructions = parse(&["set a 9223372036854775807", "add a 1", "snd a"]);
+        let mut machine = Machine::new(instructions.clone(), Silent);
+        let e = machine.run().unwrap_err();
+        assert_eq!(Fault::Overflow, e.fault);
+        assert_eq!(1, e.instruction_pointer);
+        assert_eq!("arithmetic overflow at instruction 1 (add a 1)", e.to_string());
+        assert_eq!(i64::MAX, machine.register('a'));
+
+        let mut machine = Machine::new(instructions.clone(), Silent);
+        machine.set_arithmetic(Arithmetic::Wrapping);
+        machine.run().unwrap();
+        assert_eq!(i64::MIN, machine.register('a'));
+
+        let mut machine = Machine::new(instructions, Silent);
+        machine.set_arithmetic(Arithmetic::Saturating);
+        machine.run().unwrap();
+        assert_eq!(i64::MAX, machine.register('a'));
+
+        let instructions = parse(&["set a -9223372036854775808", "mod a -1", "snd a"]);
+        let mut machine = Machine::new(instructions, Silent);
+        machine.run().unwrap();
+        assert_eq!(0, machine.register('a'));
+    }
+
+    #[test]
+    fn execution_errors() {
+        let fault = |lines: &[&str]| {
+            let mut machine = Machine::new(parse(lines), Silent);
+            machine.set_arithmetic(Arithmetic::Wrapping);
+            machine.limit_registers("abc");
+            machine.run().unwrap_err().fault
+        };
+        assert_eq!(Fault::DivideByZero, fault(&["set a 5", "mod a b"]));
+        assert_eq!(Fault::UnknownRegister('z'), fault(&["set a 5", "add z a"]));
+        assert_eq!(
+            Fault::JumpOutOfBounds,
+            fault(&["set a 9223372036854775807", "snd a", "jgz 1 a"])
+        );
+    }
+
+    #[test]
+    fn jumps_outside_the_program_halt() {
+        let halts = |lines: &[&str]| {
+            let mut machine = Machine::new(parse(lines), Silent);
+            machine.run()
+        };
+        assert_eq!(Ok(Status::Halted), halts(&["jnz 1 2", "snd a"]));
+        assert_eq!(Ok(Status::Halted), halts(&["set b 40", "jnz 1 b", "snd a"]));
+        assert_eq!(Ok(Status::Halted), halts(&["set a 5", "jgz a -2"]));
+        assert_eq!(
+            Ok(Status::Halted),
+            halts(&["set a -9223372036854775808", "snd a", "jgz 1 a"])
+        );
+    }
+
+    #[test]
+    fn rcv_number_discards_message() {
+        let mut machine = Machine::new(parse(&["rcv 0", "rcv a"]), Messages::new());
+        machine.io_mut().deliver(5);
+        machine.io_mut().deliver(7);
+        assert_eq!(Ok(Status::Halted), machine.run());
+        assert_eq!(7, machine.register('a'));
+        assert!(!machine.io().has_pending());
+    }
+
+    #[test]
+    fn validate_duet() {
+        let instructions = parse(&["snd 1", "rcv 0", "sub a 1", "jgz a -2", "jnz a -3"]);
+        assert!(validate(&instructions[..2]).is_empty());
+
+        let unsupported = validate(&instructions);
+        let indexes: Vec<usize> = unsupported.iter().map(|u| u.index).collect();
+        assert_eq!(vec![2, 4], indexes);
+        assert_eq!(
+            "instruction 2 (sub a 1): not in the duet instruction set",
+            unsupported[0].to_string()
+        );
+    }
+
+    #[test]
+    fn debugger_session() {
+        let instructions = parse(&["set a 1", "add a 2", "mul a a", "jgz a 2", "set b 7", "snd a"]);
+        let mut machine = Machine::new(instructions, Silent);
+        let commands = "break 3\nstep\ncontinue\nprint a\nregisters\ncontinue\n";
+        let mut output = Vec::new();
+
+        let status = Debugger::new()
+            .run(&mut machine, commands.as_bytes(), &mut output)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert_eq!(Status::Halted, status);
+        assert!(output.contains("   0: set a 1  a: 0 -> 1"));
+        assert!(output.contains("breakpoint at 3\n   3: jgz a 2"));
+        assert!(output.contains("a = 9\n"));
+        assert!(output.contains("a = 9, b = 0"));
+        assert!(output.ends_with("halted\nhalted at 6\n(debug) "));
+    }
+}
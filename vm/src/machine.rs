@@ -0,0 +1,295 @@
+use arithmetic::Arithmetic;
+use bytecode::{self, Jump, Op, Value};
+use error::{Fault, VmError};
+use instruction::{Instruction, Operand};
+use io::{Io, Received};
+use trace::{Change, TraceEvent, Tracer};
+
+use std::fmt::{self, Debug, Formatter};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Status {
+    // The instruction was executed and there is more to do
+    Running,
+    // The I/O asked for execution to stop at a rcv
+    Stopped,
+    // The instruction pointer has left the program
+    Halted,
+}
+
+//
+// The machine executes the bytecode compiled from its code segment.
+// Registers live in slots, indexed by their position in names; the
+// instructions are kept for inspection.
+//
+pub struct Machine<I: Io> {
+    code_segment: Vec<Instruction>,
+    bytecode: Vec<Op>,
+    instruction_pointer: i64,
+    names: Vec<char>,
+    registers: Vec<i64>,
+    // For each instruction, a register it uses which the machine does
+    // not have; empty when any register may be used
+    unknown: Vec<Option<char>>,
+    arithmetic: Arithmetic,
+    io: I,
+    tracer: Option<Box<dyn Tracer>>,
+}
+
+impl<I: Io + Debug> Debug for Machine<I> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Machine")
+            .field("code_segment", &self.code_segment)
+            .field("instruction_pointer", &self.instruction_pointer)
+            .field("registers", &self.registers())
+            .field("arithmetic", &self.arithmetic)
+            .field("io", &self.io)
+            .field("tracing", &self.tracer.is_some())
+            .finish()
+    }
+}
+
+impl<I: Io> Machine<I> {
+    // Every register named in the program starts out as zero
+    pub fn new(instructions: Vec<Instruction>, io: I) -> Machine<I> {
+        let mut names = Vec::new();
+        let bytecode = bytecode::compile(&instructions, &mut names);
+        let registers = vec![0; names.len()];
+
+        Machine {
+            code_segment: instructions,
+            bytecode,
+            instruction_pointer: 0,
+            names,
+            registers,
+            unknown: Vec::new(),
+            arithmetic: Arithmetic::default(),
+            io,
+            tracer: None,
+        }
+    }
+
+    pub fn set_arithmetic(&mut self, arithmetic: Arithmetic) {
+        self.arithmetic = arithmetic;
+    }
+
+    //
+    // Give the machine only the registers named in allowed, each set
+    // to zero. An instruction using any other register is an error.
+    //
+    pub fn limit_registers(&mut self, allowed: &str) {
+        for register in allowed.chars() {
+            self.set_register(register, 0);
+        }
+        self.unknown = self.code_segment
+            .iter()
+            .map(|instruction| {
+                instruction
+                    .operands()
+                    .into_iter()
+                    .filter_map(|operand| match operand {
+                        Operand::Reg(register) => Some(register),
+                        Operand::Imm(_) => None,
+                    })
+                    .find(|&register| !allowed.contains(register))
+            })
+            .collect();
+    }
+
+    pub fn set_tracer(&mut self, tracer: Option<Box<dyn Tracer>>) {
+        self.tracer = tracer;
+    }
+
+    pub fn register(&self, register: char) -> i64 {
+        match self.names.iter().position(|&name| name == register) {
+            Some(slot) => self.registers[slot],
+            None => 0,
+        }
+    }
+
+    // A register not used by the program is given a slot of its own
+    pub fn set_register(&mut self, register: char, new_value: i64) {
+        let slot = bytecode::slot(&mut self.names, register);
+        if slot == self.registers.len() {
+            self.registers.push(new_value);
+        } else {
+            self.registers[slot] = new_value;
+        }
+    }
+
+    // Every register, in alphabetical order
+    pub fn registers(&self) -> Vec<(char, i64)> {
+        let mut registers: Vec<(char, i64)> = self.names
+            .iter()
+            .cloned()
+            .zip(self.registers.iter().cloned())
+            .collect();
+        registers.sort();
+        registers
+    }
+
+    pub fn io(&self) -> &I {
+        &self.io
+    }
+
+    pub fn io_mut(&mut self) -> &mut I {
+        &mut self.io
+    }
+
+    pub fn instruction_pointer(&self) -> i64 {
+        self.instruction_pointer
+    }
+
+    pub fn set_instruction_pointer(&mut self, instruction_pointer: i64) {
+        self.instruction_pointer = instruction_pointer;
+    }
+
+    pub fn code_segment(&self) -> &[Instruction] {
+        &self.code_segment
+    }
+
+    // The instruction about to be executed, if still within the program
+    pub fn current_instruction(&self) -> Option<&Instruction> {
+        if self.instruction_pointer < 0 {
+            return None;
+        }
+        self.code_segment.get(self.instruction_pointer as usize)
+    }
+
+    fn value(&self, value: Value) -> i64 {
+        match value {
+            Value::Slot(slot) => self.registers[slot],
+            Value::Imm(number) => number,
+        }
+    }
+
+    fn update<F>(&mut self, slot: usize, y: Value, f: F) -> Result<(), Fault>
+    where
+        F: Fn(Arithmetic, i64, i64) -> Result<i64, Fault>,
+    {
+        let y = self.value(y);
+        self.registers[slot] = f(self.arithmetic, self.registers[slot], y)?;
+        Ok(())
+    }
+
+    // Landing anywhere outside the program ends it, as running off the
+    // end does; only a target beyond the range of an i64 is an error
+    fn jump(&self, jump: Jump) -> Result<i64, Fault> {
+        match jump {
+            Jump::To(target) => Ok(target),
+            Jump::Overflow => Err(Fault::JumpOutOfBounds),
+            Jump::By(slot) => self.instruction_pointer
+                .checked_add(self.registers[slot])
+                .ok_or(Fault::JumpOutOfBounds),
+        }
+    }
+
+    pub fn step(&mut self) -> Result<Status, VmError> {
+        if self.instruction_pointer < 0 {
+            return Ok(Status::Halted);
+        }
+        let op = match self.bytecode.get(self.instruction_pointer as usize) {
+            Some(op) => *op,
+            None => return Ok(Status::Halted),
+        };
+
+        if self.tracer.is_none() {
+            return self.execute(op);
+        }
+
+        let instruction_pointer = self.instruction_pointer;
+        let instruction = self.code_segment[instruction_pointer as usize];
+        let before = self.registers();
+        let status = self.execute(op)?;
+
+        // A rcv which stopped the machine will be executed again
+        if status == Status::Running {
+            let changes: Vec<Change> = self.registers()
+                .into_iter()
+                .zip(before)
+                .map(|((register, new), (_, old))| Change { register, old, new })
+                .filter(|change| change.old != change.new)
+                .collect();
+
+            let event = TraceEvent {
+                instruction_pointer,
+                instruction,
+                changes,
+            };
+            if let Some(ref mut tracer) = self.tracer {
+                tracer.trace(&event);
+            }
+        }
+
+        Ok(status)
+    }
+
+    fn execute(&mut self, op: Op) -> Result<Status, VmError> {
+        let ip = self.instruction_pointer;
+        if let Some(&Some(register)) = self.unknown.get(ip as usize) {
+            return Err(self.fault(Fault::UnknownRegister(register)));
+        }
+        self.execute_op(op).map_err(|fault| self.fault(fault))
+    }
+
+    fn fault(&self, fault: Fault) -> VmError {
+        VmError {
+            instruction_pointer: self.instruction_pointer,
+            instruction: self.code_segment[self.instruction_pointer as usize],
+            fault,
+        }
+    }
+
+    fn execute_op(&mut self, op: Op) -> Result<Status, Fault> {
+        let mut next = self.instruction_pointer + 1;
+        match op {
+            Op::Snd(x) => {
+                let value = self.value(x);
+                self.io.snd(value);
+            }
+            Op::Set(slot, y) => self.update(slot, y, |_, _, y| Ok(y))?,
+            Op::Add(slot, y) => self.update(slot, y, Arithmetic::add)?,
+            Op::Sub(slot, y) => self.update(slot, y, Arithmetic::sub)?,
+            Op::Mul(slot, y) => self.update(slot, y, Arithmetic::mul)?,
+            Op::Mod(slot, y) => self.update(slot, y, Arithmetic::rem)?,
+            Op::Rcv(x) => {
+                let value = self.value(x);
+                match self.io.rcv(value) {
+                    // A number has nowhere to keep the value, which is
+                    // received all the same
+                    Received::Value(received) => {
+                        if let Value::Slot(slot) = x {
+                            self.registers[slot] = received;
+                        }
+                    }
+                    Received::Ignored => {}
+                    Received::Stop => return Ok(Status::Stopped),
+                }
+            }
+            Op::Jgz(x, y) => {
+                if self.value(x) > 0 {
+                    next = self.jump(y)?;
+                }
+            }
+            Op::Jnz(x, y) => {
+                if self.value(x) != 0 {
+                    next = self.jump(y)?;
+                }
+            }
+        }
+        self.instruction_pointer = next;
+
+        Ok(Status::Running)
+    }
+
+    // Execute until the I/O stops the program, it runs off the end of
+    // the code segment, or an instruction fails
+    pub fn run(&mut self) -> Result<Status, VmError> {
+        loop {
+            let status = self.step()?;
+            if status != Status::Running {
+                return Ok(status);
+            }
+        }
+    }
+}
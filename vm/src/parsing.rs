@@ -0,0 +1,52 @@
+use instruction::{Instruction, Operand};
+
+use pest::Parser;
+use solver::ParseError;
+
+#[derive(Parser)]
+#[grammar = "instruction.pest"]
+struct InstructionParser;
+
+// Numbers are matched by the grammar, but may still be too large to
+// hold; token is the slice of line holding the number.
+fn parse_number(line: &str, token: &str) -> Result<i64, ParseError> {
+    token.parse().map_err(|e| ParseError::token(1, line, token, e))
+}
+
+pub fn parse_line(line: &str) -> Result<Instruction, ParseError> {
+    let mut pairs = InstructionParser::parse(Rule::line, line).map_err(ParseError::from_pest)?;
+
+    // The grammar matches exactly one instruction per line
+    let pair = pairs.next().unwrap();
+    let rule = pair.as_rule();
+
+    let mut operands: Vec<Operand> = Vec::new();
+    for inner in pair.into_inner() {
+        let text = inner.clone().into_span().as_str();
+        match inner.as_rule() {
+            Rule::register => {
+                operands.push(Operand::Reg(text.chars().next().unwrap()));
+            }
+            Rule::number => {
+                operands.push(Operand::Imm(parse_number(line, text)?));
+            }
+            rule => unreachable!("unexpected rule {:?}", rule),
+        }
+    }
+
+    // The grammar only allows a register as the target of a write
+    let instruction = match (rule, operands.as_slice()) {
+        (Rule::snd, &[x]) => Instruction::Snd(x),
+        (Rule::set, &[Operand::Reg(register), y]) => Instruction::Set(register, y),
+        (Rule::add, &[Operand::Reg(register), y]) => Instruction::Add(register, y),
+        (Rule::sub, &[Operand::Reg(register), y]) => Instruction::Sub(register, y),
+        (Rule::mul, &[Operand::Reg(register), y]) => Instruction::Mul(register, y),
+        (Rule::modulo, &[Operand::Reg(register), y]) => Instruction::Mod(register, y),
+        (Rule::rcv, &[x]) => Instruction::Rcv(x),
+        (Rule::jgz, &[x, y]) => Instruction::Jgz(x, y),
+        (Rule::jnz, &[x, y]) => Instruction::Jnz(x, y),
+        (rule, _) => unreachable!("unexpected rule {:?}", rule),
+    };
+
+    Ok(instruction)
+}
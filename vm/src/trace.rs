@@ -0,0 +1,46 @@
+use instruction::Instruction;
+
+use std::fmt::{Display, Error, Formatter};
+
+// A register written by an instruction
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Change {
+    pub register: char,
+    pub old: i64,
+    pub new: i64,
+}
+
+// One executed instruction, and the registers it changed
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceEvent {
+    pub instruction_pointer: i64,
+    pub instruction: Instruction,
+    pub changes: Vec<Change>,
+}
+
+impl Display for TraceEvent {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "{:>4}: {}", self.instruction_pointer, self.instruction)?;
+        for change in &self.changes {
+            write!(f, "  {}: {} -> {}", change.register, change.old, change.new)?;
+        }
+        Ok(())
+    }
+}
+
+//
+// A tracer is told about every instruction the machine executes. Any
+// closure taking a &TraceEvent will do.
+//
+pub trait Tracer {
+    fn trace(&mut self, event: &TraceEvent);
+}
+
+impl<F> Tracer for F
+where
+    F: FnMut(&TraceEvent),
+{
+    fn trace(&mut self, event: &TraceEvent) {
+        self(event)
+    }
+}
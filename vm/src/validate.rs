@@ -0,0 +1,48 @@
+use instruction::Instruction;
+
+use std::fmt::{self, Display, Formatter};
+
+// An instruction the duet puzzle does not allow
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unsupported {
+    pub index: usize,
+    pub instruction: Instruction,
+    pub reason: &'static str,
+}
+
+impl Display for Unsupported {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "instruction {} ({}): {}",
+            self.index, self.instruction, self.reason
+        )
+    }
+}
+
+fn check(instruction: &Instruction) -> Option<&'static str> {
+    match *instruction {
+        Instruction::Sub(_, _) | Instruction::Jnz(_, _) => Some("not in the duet instruction set"),
+        _ => None,
+    }
+}
+
+//
+// Check a duet program before it is run, returning every instruction
+// which the duet puzzle does not define. The machine will run them
+// regardless, as on the coprocessor. Every operand form of snd and rcv
+// is allowed, whether sounds or messages are meant: a rcv of a number
+// that receives a message discards it.
+//
+pub fn validate(code: &[Instruction]) -> Vec<Unsupported> {
+    code.iter()
+        .enumerate()
+        .filter_map(|(index, instruction)| {
+            check(instruction).map(|reason| Unsupported {
+                index,
+                instruction: *instruction,
+                reason,
+            })
+        })
+        .collect()
+}